@@ -1,10 +1,12 @@
-use crate::config::file::StorageConfiguration;
+use crate::config::file::{ClusterMode, StorageConfiguration};
 use crate::config::KustodioConfiguration;
+use crate::error::Error;
 use crate::handler::Handler;
 use crate::server;
 use crate::storage;
-use crate::swarm::Swarm;
+use crate::swarm::{self, Raft, Swarm};
 use ctrlc;
+use std::path::Path;
 use std::sync::mpsc::channel;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -24,13 +26,36 @@ impl App {
         let storage = match self.config.storage.clone() {
             StorageConfiguration::Memory(config) => storage::memory::Memory::new(config),
         };
-        let handler = Handler::new(storage);
+        if self.config.cluster.quorum > self.config.cluster.peers.len() {
+            return Err(Error::Config(format!(
+                "Quorum of {} exceeds the {} peers",
+                self.config.cluster.quorum,
+                self.config.cluster.peers.len()
+            ))
+            .into());
+        }
+        let (outbox, outgoing) = tokio::sync::mpsc::unbounded_channel();
+        let handler = Handler::new(storage, self.config.cluster.address.clone(), outbox);
         let swarm = Arc::new(Mutex::new(Swarm::new(
             Some(self.config.cluster.address.clone()),
             Some(self.config.cluster.peers.clone()),
             handler.clone(),
         )));
-        swarm.lock().unwrap().start()?;
+        let raft = match self.config.cluster.mode {
+            ClusterMode::Gossip => {
+                swarm.lock().unwrap().start()?;
+                None
+            }
+            ClusterMode::Raft => Some(Raft::new(
+                self.config.cluster.address.clone(),
+                self.config.api.grpc_address.clone(),
+                self.config.cluster.peers.clone(),
+                handler.clone(),
+                Path::new(self.config.cluster.data_dir.as_deref().ok_or_else(|| {
+                    Error::Config(String::from("Raft mode requires cluster.data_dir"))
+                })?),
+            )?),
+        };
 
         let (tx, rx) = channel();
         ctrlc::set_handler(move || {
@@ -43,17 +68,56 @@ impl App {
         info!("Starting grpc api...");
         threads.push(tokio::task::spawn(server::grpc::serve(
             self.config.api.grpc_address.parse().unwrap(),
-            handler,
+            handler.clone(),
             swarm_clone,
+            raft.clone(),
+            self.config.cluster.quorum,
+            self.config.cluster.peers.clone(),
+            self.config.api.allow_force,
+        )));
+        match raft.clone() {
+            Some(raft) => {
+                info!("Starting raft...");
+                threads.push(tokio::task::spawn(
+                    raft.serve(self.config.cluster.address.parse()?),
+                ));
+            }
+            None => {
+                info!("Starting gossip outbox...");
+                threads.push(tokio::task::spawn(swarm::forward(
+                    outgoing,
+                    Arc::clone(&swarm),
+                )));
+                info!("Starting state sync...");
+                threads.push(tokio::task::spawn(server::syncer::serve(
+                    handler.clone(),
+                    server::syncer::SYNC_INTERVAL,
+                )));
+            }
+        }
+        info!("Starting lease reaper...");
+        threads.push(tokio::task::spawn(server::reaper::serve(
+            handler.clone(),
+            raft.clone(),
+            server::reaper::REAP_INTERVAL,
+        )));
+        info!("Starting deadlock detector...");
+        threads.push(tokio::task::spawn(server::detector::serve(
+            handler.clone(),
+            raft,
+            server::detector::DETECT_INTERVAL,
         )));
         info!("Starting http server...");
         threads.push(tokio::task::spawn(server::http::serve(
             self.config.api.http_address.parse().unwrap(),
             self.config.clone(),
+            handler,
         )));
         info!("Waiting for Ctrl-C...");
         rx.recv().expect("Could not receive from channel.");
-        swarm.lock().unwrap().shutdown()?;
+        if self.config.cluster.mode == ClusterMode::Gossip {
+            swarm.lock().unwrap().shutdown()?;
+        }
         info!("Got it! Exiting...");
         Ok(())
     }
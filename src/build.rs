@@ -7,6 +7,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Build Protobufs ===");
     prost_build::compile_protos(&["proto/swarm.proto"], &["proto"])?;
     tonic_build::compile_protos("proto/api.proto")?;
+    tonic_build::compile_protos("proto/raft.proto")?;
     protoc_rust::Codegen::new()
         .out_dir("ui/src/proto")
         .inputs(&["proto/api.proto"])
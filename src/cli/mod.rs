@@ -1,9 +1,15 @@
 use crate::app::App;
 use crate::client::Client;
-use crate::proto::api::lock_response::Body;
+use crate::proto::api::{
+    list_request::StateFilter, list_response, lock_event, lock_response::Body, Metadata, Mode,
+};
+use crate::proto::{LeaderResponse, LockResponse};
 use clap::Parser;
 use sysinfo::{ProcessExt, Signal, System, SystemExt};
 
+/// Leadership lease of `elect` in milliseconds if no ttl is given.
+const ELECTION_TTL: u64 = 10_000;
+
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
 #[clap(propagate_version = true)]
@@ -15,7 +21,7 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     Server(ServerCommands),
-    Client(ClientCommands),
+    Client(Box<ClientCommands>),
     Stop,
 }
 
@@ -30,18 +36,93 @@ pub struct ClientCommands {
     #[clap(arg_enum)]
     pub command: ClientArgs,
     pub lock: Option<String>,
+    /// Identity the lock is held by, required to lock or unlock
+    #[clap(long, default_value = "")]
+    pub holder: String,
+    /// Unlock even if the lock is held by someone else
+    #[clap(long)]
+    pub force: bool,
+    /// Lease duration of a lock in milliseconds, 0 never expires
+    #[clap(long, default_value = "0")]
+    pub ttl: u64,
+    /// Milliseconds to wait for a lock on acquire, 0 waits until interrupted
+    #[clap(long, default_value = "0")]
+    pub timeout: u64,
+    /// Lock or acquire in shared (reader) mode
+    #[clap(long)]
+    pub shared: bool,
+    /// Total permits of a semaphore on create, permits to take or give back otherwise
+    #[clap(long, default_value = "0")]
+    pub permits: u32,
+    /// Create a lock its holder may take again
+    #[clap(long)]
+    pub reentrant: bool,
+    /// Parties of a new barrier, initial count of a new latch or amount to count down
+    #[clap(long, default_value = "1")]
+    pub count: u32,
+    /// Only list locks in this state
+    #[clap(long, arg_enum, default_value = "any")]
+    pub state: ListState,
+    /// Locks fetched per request when listing, 0 for the server default
+    #[clap(long, default_value = "0")]
+    pub page_size: u32,
+    /// Revision to resume watching from, 0 only shows new events
+    #[clap(long, default_value = "0")]
+    pub revision: u64,
+    /// Only watch these events, like locked or unlocked
+    #[clap(long = "event", multiple_occurrences = true)]
+    pub events: Vec<String>,
+    /// Lock or acquire everything below the lock as well, remove or watch it all
+    #[clap(long)]
+    pub subtree: bool,
+    /// Description attached to the lock on create or lock
+    #[clap(long, default_value = "")]
+    pub description: String,
+    /// Label attached to the lock on create or lock, as key=value
+    #[clap(long = "label", multiple_occurrences = true)]
+    pub labels: Vec<String>,
+    /// Opaque payload attached to the lock on create or lock
+    #[clap(long, default_value = "")]
+    pub payload: String,
+    /// Command to run while leading, after `--`
+    #[clap(last = true)]
+    pub exec: Vec<String>,
 }
 
 #[derive(Clone, ArgEnum)]
 pub enum ClientArgs {
     Lock,
+    Acquire,
+    /// Acquire a comma separated list of locks all at once
+    AcquireMany,
     Unlock,
+    KeepAlive,
     Peers,
     State,
     Create,
     Remove,
     List,
     Watch,
+    /// Run a command while leading an election
+    Elect,
+    /// Show the leader of an election
+    Leader,
+    /// Stream leadership changes of an election
+    Observe,
+    CreateBarrier,
+    /// Wait at a barrier until enough participants arrived
+    Enter,
+    CreateLatch,
+    CountDown,
+    /// Wait until a latch counted down to zero
+    Wait,
+}
+
+#[derive(Clone, ArgEnum)]
+pub enum ListState {
+    Any,
+    Locked,
+    Unlocked,
 }
 
 impl Cli {
@@ -66,95 +147,246 @@ impl Cli {
                 }
             }
             Commands::Client(config) => {
-                let client = Client::new(config.server.clone()).await?;
-                match config.command {
-                    ClientArgs::Lock => {
-                        println!(
-                            "{}",
-                            client
-                                .lock(get_lock_or_fail(config)?)
-                                .await
-                                .map(|_| { "Ok" })?
-                        )
+                if let Err(err) = Self::client(config).await {
+                    match err.downcast_ref::<tonic::Status>() {
+                        Some(status) => eprintln!("{:?}: {}", status.code(), status.message()),
+                        None => eprintln!("Error: {}", err),
                     }
-                    ClientArgs::Unlock => {
-                        println!(
-                            "{}",
-                            client
-                                .unlock(get_lock_or_fail(config)?)
-                                .await
-                                .map(|_| { "Ok" })?
-                        )
+                    std::process::exit(1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn client(config: &ClientCommands) -> Result<(), anyhow::Error> {
+        let client = Client::new(config.server.clone()).await?;
+        match config.command {
+            ClientArgs::Lock => {
+                let name = get_lock_or_fail(config)?;
+                let response = match config.subtree {
+                    true => {
+                        client
+                            .lock_subtree(name, config.holder.clone(), config.ttl)
+                            .await?
                     }
-                    ClientArgs::Peers => {
-                        println!("Peers:");
-                        client.peers().await.map(|res| {
-                            for peer in res.peers.clone() {
-                                println!("- {}", peer.cluster_address)
-                            }
-                        })?;
+                    false => {
+                        client
+                            .lock(
+                                name,
+                                config.holder.clone(),
+                                config.ttl,
+                                get_mode(config),
+                                config.permits,
+                                get_metadata(config)?,
+                            )
+                            .await?
                     }
-                    ClientArgs::State => {
-                        println!(
-                            "{}",
-                            match client
-                                .state(get_lock_or_fail(config)?)
-                                .await
-                                .map(|res| { res.body })?
-                            {
-                                None => String::new(),
-                                Some(body) => match body {
-                                    Body::Error(err) => err,
-                                    Body::State(state) => match state {
-                                        true => String::from("Locked"),
-                                        false => String::from("Unlocked"),
-                                    },
-                                },
-                            }
-                        )
+                };
+                println!("{}", describe_token(&response))
+            }
+            ClientArgs::Acquire => {
+                let name = get_lock_or_fail(config)?;
+                let response = match config.subtree {
+                    true => {
+                        client
+                            .acquire_subtree(
+                                name,
+                                config.holder.clone(),
+                                config.ttl,
+                                config.timeout,
+                            )
+                            .await?
                     }
-                    ClientArgs::Remove => {
-                        println!(
-                            "{}",
-                            client
-                                .remove(get_lock_or_fail(config)?)
-                                .await
-                                .map(|_| { "Ok" })?
-                        )
+                    false => {
+                        client
+                            .acquire(
+                                name,
+                                config.holder.clone(),
+                                config.ttl,
+                                config.timeout,
+                                get_mode(config),
+                                config.permits,
+                            )
+                            .await?
                     }
-                    ClientArgs::Create => {
-                        println!(
-                            "{}",
-                            client
-                                .create(get_lock_or_fail(config)?)
-                                .await
-                                .map(|_| { "Created" })?
+                };
+                println!("{}", describe_token(&response))
+            }
+            ClientArgs::AcquireMany => {
+                let names = get_lock_or_fail(config)?
+                    .split(',')
+                    .map(String::from)
+                    .collect();
+                let response = client
+                    .lock_many(names, config.holder.clone(), config.ttl, config.timeout)
+                    .await?;
+                println!("Ok, tokens: {:?}", response.tokens)
+            }
+            ClientArgs::Unlock => {
+                println!(
+                    "{}",
+                    client
+                        .unlock(
+                            get_lock_or_fail(config)?,
+                            config.holder.clone(),
+                            config.force,
+                            config.permits
                         )
+                        .await
+                        .map(|_| { "Ok" })?
+                )
+            }
+            ClientArgs::KeepAlive => {
+                let lock = get_lock_or_fail(config)?;
+                let mut keep_alive = client
+                    .keep_alive(lock.clone(), config.holder.clone(), config.ttl)
+                    .await?;
+                println!("Keeping {} alive, press Ctrl-C to stop", lock);
+                return Err(keep_alive.stopped().await);
+            }
+            ClientArgs::Peers => {
+                println!("Peers:");
+                client.peers().await.map(|res| {
+                    for peer in res.peers.clone() {
+                        println!("- {}", peer.cluster_address)
                     }
-                    ClientArgs::List => {
-                        println!("List:");
-                        for lock in client.list().await?.locks {
-                            println!(
-                                "- {}: {}",
-                                lock.name,
-                                match lock.state {
-                                    true => "Locked",
-                                    false => "Unlocked",
-                                }
-                            )
-                        }
+                })?;
+            }
+            ClientArgs::State => {
+                let response = client
+                    .state(get_lock_or_fail(config)?, config.holder.clone())
+                    .await?;
+                println!(
+                    "{}",
+                    match response.body {
+                        None => String::new(),
+                        Some(body) => match body {
+                            Body::Error(err) => err,
+                            Body::Token(token) => token.to_string(),
+                            Body::State(state) => match state {
+                                true => String::from("Locked"),
+                                false => String::from("Unlocked"),
+                            },
+                        },
                     }
-                    ClientArgs::Watch => {
-                        println!("Watching stream of changes:");
-                        let mut event_stream = client.watch().await?;
-                        while let Some(event) = event_stream.message().await? {
-                            println!(
-                                "\tLock: {}, State: {:?}",
-                                event.name,
-                                crate::proto::api::lock_event::Status::from_i32(event.status)
-                                    .unwrap()
-                            );
-                        }
+                );
+                if response.position > 0 {
+                    println!("Waiting at position {}", response.position);
+                }
+                if let Some(metadata) = response.metadata {
+                    print_metadata(&metadata);
+                    if !metadata.payload.is_empty() {
+                        println!("  payload: {}", String::from_utf8_lossy(&metadata.payload));
+                    }
+                }
+            }
+            ClientArgs::Remove => {
+                println!(
+                    "{}",
+                    client
+                        .remove(get_lock_or_fail(config)?, config.subtree)
+                        .await
+                        .map(|_| { "Ok" })?
+                )
+            }
+            ClientArgs::Create => {
+                println!(
+                    "{}",
+                    client
+                        .create(
+                            get_lock_or_fail(config)?,
+                            config.permits,
+                            config.reentrant,
+                            get_metadata(config)?
+                        )
+                        .await
+                        .map(|_| { "Created" })?
+                )
+            }
+            ClientArgs::List => {
+                println!("List:");
+                let mut page_token = String::new();
+                loop {
+                    let page = client
+                        .list(
+                            config.lock.clone().unwrap_or_default(),
+                            get_state_filter(config),
+                            config.page_size,
+                            page_token,
+                        )
+                        .await?;
+                    print_locks(page.locks);
+                    if page.next_page_token.is_empty() {
+                        break;
+                    }
+                    page_token = page.next_page_token;
+                }
+            }
+            ClientArgs::Elect => return elect(&client, config).await,
+            ClientArgs::Leader => {
+                let leader = client.leader(get_lock_or_fail(config)?).await?;
+                println!("{}", describe_leader(&leader));
+            }
+            ClientArgs::Observe => {
+                let mut leaders = client.observe(get_lock_or_fail(config)?).await?;
+                while let Some(leader) = leaders.message().await? {
+                    println!("{}", describe_leader(&leader));
+                }
+            }
+            ClientArgs::CreateBarrier => {
+                client
+                    .create_barrier(get_lock_or_fail(config)?, config.count)
+                    .await?;
+                println!("Created")
+            }
+            ClientArgs::Enter => {
+                let barrier = client
+                    .enter(
+                        get_lock_or_fail(config)?,
+                        get_holder_or_default(config),
+                        config.timeout,
+                    )
+                    .await?;
+                println!("Ok, generation: {}", barrier.generation)
+            }
+            ClientArgs::CreateLatch => {
+                client
+                    .create_latch(get_lock_or_fail(config)?, config.count)
+                    .await?;
+                println!("Created")
+            }
+            ClientArgs::CountDown => {
+                let latch = client
+                    .count_down(get_lock_or_fail(config)?, config.count)
+                    .await?;
+                println!("Ok, count: {}", latch.count)
+            }
+            ClientArgs::Wait => {
+                client
+                    .wait(get_lock_or_fail(config)?, config.timeout)
+                    .await?;
+                println!("Ok")
+            }
+            ClientArgs::Watch => {
+                let statuses = get_statuses(config)?;
+                let (name, prefix) = match (config.lock.clone(), config.subtree) {
+                    (Some(lock), true) => (String::new(), lock),
+                    (lock, _) => (lock.unwrap_or_default(), String::new()),
+                };
+                let mut event_stream = client
+                    .watch(name, prefix, statuses, config.revision)
+                    .await?;
+                println!("Watching stream of changes:");
+                while let Some(event) = event_stream.message().await? {
+                    println!(
+                        "\t{} Lock: {}, State: {:?}",
+                        event.revision,
+                        event.name,
+                        lock_event::Status::from_i32(event.status).unwrap()
+                    );
+                    if !event.description.is_empty() {
+                        println!("\t\t{}", event.description);
                     }
                 }
             }
@@ -163,9 +395,182 @@ impl Cli {
     }
 }
 
+/// Campaigns for the election, runs the command while leading and resigns
+/// once it exits. The command gets killed if the leadership lease is lost.
+async fn elect(client: &Client, config: &ClientCommands) -> Result<(), anyhow::Error> {
+    let name = get_lock_or_fail(config)?;
+    let (program, args) = match config.exec.split_first() {
+        Some(command) => command,
+        None => {
+            return Err(anyhow::Error::msg(
+                "No command given, use: elect <name> -- <cmd>",
+            ))
+        }
+    };
+    let candidate = get_holder_or_default(config);
+    let ttl = match config.ttl {
+        0 => ELECTION_TTL,
+        ttl => ttl,
+    };
+    let leader = loop {
+        match client
+            .campaign(name.clone(), candidate.clone(), ttl, config.timeout)
+            .await
+        {
+            Ok(leader) => break leader,
+            Err(err) => match err.downcast_ref::<tonic::Status>() {
+                // Without a timeout keep campaigning until elected.
+                Some(status)
+                    if status.code() == tonic::Code::DeadlineExceeded && config.timeout == 0 =>
+                {
+                    continue
+                }
+                _ => return Err(err),
+            },
+        }
+    };
+    println!("{}", describe_leader(&leader));
+    let mut keep_alive = client
+        .keep_alive(name.clone(), candidate.clone(), ttl)
+        .await?;
+    let mut child = tokio::process::Command::new(program).args(args).spawn()?;
+    tokio::select! {
+        status = child.wait() => {
+            drop(keep_alive);
+            client.resign(name, candidate).await?;
+            let status = status?;
+            if !status.success() {
+                return Err(anyhow::Error::msg(format!("Command failed: {}", status)));
+            }
+        }
+        err = keep_alive.stopped() => {
+            child.kill().await?;
+            return Err(err.context("Lost leadership"));
+        }
+    }
+    Ok(())
+}
+
+fn get_state_filter(config: &ClientCommands) -> StateFilter {
+    match config.state {
+        ListState::Any => StateFilter::Any,
+        ListState::Locked => StateFilter::Locked,
+        ListState::Unlocked => StateFilter::Unlocked,
+    }
+}
+
+/// Event statuses named by `--event`, matched case insensitively.
+fn get_statuses(config: &ClientCommands) -> Result<Vec<lock_event::Status>, anyhow::Error> {
+    let statuses: Vec<lock_event::Status> = (0..).map_while(lock_event::Status::from_i32).collect();
+    config
+        .events
+        .iter()
+        .map(|event| {
+            statuses
+                .iter()
+                .find(|status| format!("{:?}", status).eq_ignore_ascii_case(event))
+                .copied()
+                .ok_or_else(|| anyhow::Error::msg(format!("Unknown event {:?}", event)))
+        })
+        .collect()
+}
+
+fn describe_token(response: &LockResponse) -> String {
+    match response.body {
+        Some(Body::Token(token)) => format!("Ok, token: {}", token),
+        _ => String::from("Ok"),
+    }
+}
+
+fn describe_leader(leader: &LeaderResponse) -> String {
+    match leader.leader.as_str() {
+        "" => format!("{}: no leader", leader.name),
+        name => format!("{}: led by {:?}, term {}", leader.name, name, leader.term),
+    }
+}
+
+/// The given holder, or one made up from host name and process id.
+fn get_holder_or_default(config: &ClientCommands) -> String {
+    match config.holder.as_str() {
+        "" => format!(
+            "{}-{}",
+            System::new().host_name().unwrap_or_default(),
+            std::process::id()
+        ),
+        holder => holder.to_string(),
+    }
+}
+
 fn get_lock_or_fail(config: &ClientCommands) -> Result<String, anyhow::Error> {
     match config.lock.clone() {
         Some(lock) => Ok(lock),
         None => return Err(anyhow::Error::msg("No lock specified")),
     }
 }
+
+/// Metadata from the description, labels and payload given.
+fn get_metadata(config: &ClientCommands) -> Result<Metadata, anyhow::Error> {
+    let mut labels = std::collections::HashMap::new();
+    for label in &config.labels {
+        match label.split_once('=') {
+            Some((key, value)) => labels.insert(key.to_string(), value.to_string()),
+            None => {
+                return Err(anyhow::Error::msg(format!(
+                    "Invalid label {:?}, use key=value",
+                    label
+                )))
+            }
+        };
+    }
+    Ok(Metadata {
+        labels,
+        description: config.description.clone(),
+        payload: config.payload.clone().into_bytes(),
+    })
+}
+
+fn print_locks(locks: Vec<list_response::Lock>) {
+    for lock in locks {
+        println!(
+            "- {}: {}",
+            lock.name,
+            match lock.state {
+                _ if lock.permits > 0 => format!(
+                    "{}/{} permits used by {:?}",
+                    lock.used, lock.permits, lock.holders
+                ),
+                true if lock.mode == Mode::Shared as i32 => format!("Shared by {:?}", lock.holders),
+                true if lock.subtree =>
+                    format!("Locked with everything below by {:?}", lock.holder),
+                true if lock.holds > 1 =>
+                    format!("Locked by {:?} {} times", lock.holder, lock.holds),
+                true => format!("Locked by {:?}", lock.holder),
+                false => String::from("Unlocked"),
+            }
+        );
+        if !lock.waiters.is_empty() {
+            println!("  waiting: {:?}", lock.waiters);
+        }
+        if let Some(metadata) = lock.metadata {
+            print_metadata(&metadata);
+        }
+    }
+}
+
+fn print_metadata(metadata: &Metadata) {
+    if !metadata.description.is_empty() {
+        println!("  {}", metadata.description);
+    }
+    let mut labels: Vec<_> = metadata.labels.iter().collect();
+    labels.sort();
+    for (key, value) in labels {
+        println!("  {}={}", key, value);
+    }
+}
+
+fn get_mode(config: &ClientCommands) -> Mode {
+    match config.shared {
+        true => Mode::Shared,
+        false => Mode::Exclusive,
+    }
+}
@@ -1,11 +1,35 @@
 use crate::proto::{
-    Empty, ListResponse, LockEvent, LockRequest, LockResponse, LockingClient, PeersResponse,
+    api::{list_request::StateFilter, lock_event, Metadata, Mode},
+    BarrierRequest, BarrierResponse, ElectionRequest, Empty, LatchRequest, LatchResponse,
+    LeaderResponse, ListRequest, ListResponse, LockEvent, LockManyRequest, LockManyResponse,
+    LockRequest, LockResponse, LockingClient, PeersResponse, WatchRequest,
 };
 use std::sync::{Arc, Mutex, MutexGuard};
-use tonic::{transport::Channel, Streaming};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tonic::{transport::Channel, Status, Streaming};
 
 pub struct Client(Arc<Mutex<LockingClient<Channel>>>);
 
+/// Keeps renewing a lock lease in the background until it is dropped.
+pub struct KeepAlive(JoinHandle<Status>);
+
+impl KeepAlive {
+    /// Waits until a renewal fails and returns why.
+    pub async fn stopped(&mut self) -> anyhow::Error {
+        match (&mut self.0).await {
+            Ok(status) => anyhow::Error::msg(status.message().to_string()),
+            Err(err) => anyhow::Error::msg(err.to_string()),
+        }
+    }
+}
+
+impl Drop for KeepAlive {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 impl<'a> Client {
     pub async fn new(server_address: String) -> Result<Self, anyhow::Error> {
         Ok(Client(Arc::new(Mutex::new(
@@ -17,43 +41,223 @@ impl<'a> Client {
             anyhow::Error::msg(format!("Client Lock Error: {:?}", err.to_string()))
         })?)
     }
-    pub async fn create(&self, name: String) -> Result<LockResponse, anyhow::Error> {
-        Ok(self
-            .get_client_lock()?
-            .create(LockRequest { name })
+    /// Creates a lock, or a semaphore with `permits` permits if not 0.
+    pub async fn create(
+        &self,
+        name: String,
+        permits: u32,
+        reentrant: bool,
+        metadata: Metadata,
+    ) -> Result<LockResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .create(LockRequest {
+                name,
+                permits,
+                reentrant,
+                metadata: Some(metadata),
+                ..Default::default()
+            })
             .await?
             .into_inner())
     }
-    pub async fn remove(&self, name: String) -> Result<LockResponse, anyhow::Error> {
-        Ok(self
-            .get_client_lock()?
-            .remove(LockRequest { name })
+    /// Removes the lock, with `subtree` also every lock below it.
+    pub async fn remove(&self, name: String, subtree: bool) -> Result<LockResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .remove(LockRequest {
+                name,
+                subtree,
+                ..Default::default()
+            })
             .await?
             .into_inner())
     }
 
-    pub async fn list(&self) -> Result<ListResponse, anyhow::Error> {
-        Ok(self.get_client_lock()?.list(Empty {}).await?.into_inner())
+    /// Lists a page of the locks in the `prefix` namespace, all namespaces
+    /// if it is empty. `page_token` continues where the previous page ended.
+    pub async fn list(
+        &self,
+        prefix: String,
+        state: StateFilter,
+        page_size: u32,
+        page_token: String,
+    ) -> Result<ListResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .list(ListRequest {
+                prefix,
+                state: state.into(),
+                page_size,
+                page_token,
+            })
+            .await?
+            .into_inner())
     }
 
-    pub async fn lock(&self, name: String) -> Result<LockResponse, anyhow::Error> {
-        Ok(self
-            .get_client_lock()?
-            .lock(LockRequest { name })
+    pub async fn lock(
+        &self,
+        name: String,
+        holder: String,
+        ttl: u64,
+        mode: Mode,
+        permits: u32,
+        metadata: Metadata,
+    ) -> Result<LockResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .lock(LockRequest {
+                name,
+                holder,
+                ttl,
+                mode: mode.into(),
+                permits,
+                metadata: Some(metadata),
+                ..Default::default()
+            })
             .await?
             .into_inner())
     }
-    pub async fn unlock(&self, name: String) -> Result<LockResponse, anyhow::Error> {
+    /// Locks `name` exclusively along with every lock below it.
+    pub async fn lock_subtree(
+        &self,
+        name: String,
+        holder: String,
+        ttl: u64,
+    ) -> Result<LockResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .lock(LockRequest {
+                name,
+                holder,
+                ttl,
+                subtree: true,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    /// Waits up to `timeout` milliseconds for the lock to become free and acquires it.
+    pub async fn acquire(
+        &self,
+        name: String,
+        holder: String,
+        ttl: u64,
+        timeout: u64,
+        mode: Mode,
+        permits: u32,
+    ) -> Result<LockResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .acquire(LockRequest {
+                name,
+                holder,
+                ttl,
+                timeout,
+                mode: mode.into(),
+                permits,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    /// Waits up to `timeout` milliseconds until `name` and everything below
+    /// it are free and acquires them.
+    pub async fn acquire_subtree(
+        &self,
+        name: String,
+        holder: String,
+        ttl: u64,
+        timeout: u64,
+    ) -> Result<LockResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .acquire(LockRequest {
+                name,
+                holder,
+                ttl,
+                timeout,
+                subtree: true,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    /// Acquires all `names` or none of them, waiting up to `timeout` milliseconds.
+    pub async fn lock_many(
+        &self,
+        names: Vec<String>,
+        holder: String,
+        ttl: u64,
+        timeout: u64,
+    ) -> Result<LockManyResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .acquire_many(LockManyRequest {
+                names,
+                holder,
+                ttl,
+                timeout,
+            })
+            .await?
+            .into_inner())
+    }
+    pub async fn unlock(
+        &self,
+        name: String,
+        holder: String,
+        force: bool,
+        permits: u32,
+    ) -> Result<LockResponse, anyhow::Error> {
         Ok(self
             .get_client_lock()?
-            .unlock(LockRequest { name })
+            .unlock(LockRequest {
+                name,
+                holder,
+                force,
+                permits,
+                ..Default::default()
+            })
             .await?
             .into_inner())
     }
-    pub async fn state(&self, name: String) -> Result<LockResponse, anyhow::Error> {
+    /// Renews the lease of `name` every third of `ttl` milliseconds until the
+    /// returned guard is dropped, after which the lock expires on its own.
+    pub async fn keep_alive(
+        &self,
+        name: String,
+        holder: String,
+        ttl: u64,
+    ) -> Result<KeepAlive, anyhow::Error> {
+        let request = LockRequest {
+            name,
+            holder,
+            ttl,
+            ..Default::default()
+        };
+        let mut client = self.get_client_lock()?.clone();
+        client.keep_alive(request.clone()).await?;
+        Ok(KeepAlive(tokio::spawn(async move {
+            let mut ticker =
+                tokio::time::interval(Duration::from_millis(ttl / 3).max(Duration::from_millis(1)));
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if let Err(status) = client.keep_alive(request.clone()).await {
+                    return status;
+                }
+            }
+        })))
+    }
+    /// State of `name` and the place of `holder` in its wait queue.
+    pub async fn state(&self, name: String, holder: String) -> Result<LockResponse, anyhow::Error> {
         Ok(self
             .get_client_lock()?
-            .state(LockRequest { name })
+            .state(LockRequest {
+                name,
+                holder,
+                ..Default::default()
+            })
             .await?
             .into_inner())
     }
@@ -61,7 +265,157 @@ impl<'a> Client {
         Ok(self.get_client_lock()?.peers(Empty {}).await?.into_inner())
     }
 
-    pub async fn watch(&self) -> Result<Streaming<LockEvent>, anyhow::Error> {
-        Ok(self.get_client_lock()?.watch(Empty {}).await?.into_inner())
+    /// Streams the events of lock `name`, of the locks in the `prefix`
+    /// namespace and with one of `statuses`. Empty filters match everything.
+    /// A `start_revision` other than 0 replays the events since then first.
+    pub async fn watch(
+        &self,
+        name: String,
+        prefix: String,
+        statuses: Vec<lock_event::Status>,
+        start_revision: u64,
+    ) -> Result<Streaming<LockEvent>, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .watch(WatchRequest {
+                name,
+                prefix,
+                statuses: statuses.into_iter().map(i32::from).collect(),
+                start_revision,
+            })
+            .await?
+            .into_inner())
+    }
+    /// Waits up to `timeout` milliseconds to become leader of `name`. The
+    /// leadership lease of `ttl` milliseconds has to be kept alive.
+    pub async fn campaign(
+        &self,
+        name: String,
+        candidate: String,
+        ttl: u64,
+        timeout: u64,
+    ) -> Result<LeaderResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .campaign(ElectionRequest {
+                name,
+                candidate,
+                ttl,
+                timeout,
+            })
+            .await?
+            .into_inner())
+    }
+    pub async fn resign(
+        &self,
+        name: String,
+        candidate: String,
+    ) -> Result<LeaderResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .resign(ElectionRequest {
+                name,
+                candidate,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    pub async fn leader(&self, name: String) -> Result<LeaderResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .leader(ElectionRequest {
+                name,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    /// Creates a barrier that lets participants through once `parties` entered.
+    pub async fn create_barrier(
+        &self,
+        name: String,
+        parties: u32,
+    ) -> Result<BarrierResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .create_barrier(BarrierRequest {
+                name,
+                parties,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    /// Waits up to `timeout` milliseconds at the barrier for the other participants.
+    pub async fn enter(
+        &self,
+        name: String,
+        participant: String,
+        timeout: u64,
+    ) -> Result<BarrierResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .enter(BarrierRequest {
+                name,
+                participant,
+                timeout,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    pub async fn create_latch(
+        &self,
+        name: String,
+        count: u32,
+    ) -> Result<LatchResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .create_latch(LatchRequest {
+                name,
+                count,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    pub async fn count_down(
+        &self,
+        name: String,
+        amount: u32,
+    ) -> Result<LatchResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .count_down(LatchRequest {
+                name,
+                count: amount,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    /// Waits up to `timeout` milliseconds for the latch to reach zero.
+    pub async fn wait(&self, name: String, timeout: u64) -> Result<LatchResponse, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .wait(LatchRequest {
+                name,
+                timeout,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
+    }
+    /// Streams the leader of `name`, starting with the current one.
+    pub async fn observe(&self, name: String) -> Result<Streaming<LeaderResponse>, anyhow::Error> {
+        let mut client = self.get_client_lock()?.clone();
+        Ok(client
+            .observe(ElectionRequest {
+                name,
+                ..Default::default()
+            })
+            .await?
+            .into_inner())
     }
 }
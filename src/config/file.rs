@@ -1,3 +1,4 @@
+use crate::error::Error;
 use crate::storage::memory;
 use config::Config;
 use serde::{Deserialize, Serialize};
@@ -14,12 +15,42 @@ pub struct KustodioConfiguration {
 pub struct ClusterConfiguration {
     pub address: String,
     pub peers: Vec<String>,
+    #[serde(default)]
+    pub mode: ClusterMode,
+    /// Peers that must acknowledge a lock in gossip mode before it is
+    /// granted, 0 grants it right away.
+    #[serde(default)]
+    pub quorum: usize,
+    /// Directory raft mode keeps its term, vote and log in, required there.
+    #[serde(default)]
+    pub data_dir: Option<String>,
+}
+
+/// How lock operations reach the peers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClusterMode {
+    /// Applied locally and gossiped, stays available during partitions.
+    Gossip,
+    /// Replicated through a Raft log, only committed once a majority of the
+    /// peers has it.
+    Raft,
+}
+
+impl Default for ClusterMode {
+    fn default() -> Self {
+        ClusterMode::Gossip
+    }
 }
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct ApiConfiguration {
     pub grpc_address: String,
     pub http_address: String,
+    /// Lets Unlock release a lock held by someone else with `force`. Callers
+    /// are not authenticated, so anyone reaching the api may then do so.
+    #[serde(default)]
+    pub allow_force: bool,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -29,10 +60,11 @@ pub enum StorageConfiguration {
 }
 
 impl KustodioConfiguration {
-    pub fn new(path: String) -> Result<Self, anyhow::Error> {
-        Ok(Config::builder()
+    pub fn new(path: String) -> Result<Self, Error> {
+        Config::builder()
             .add_source(config::File::from(Path::new(&path)))
-            .build()?
-            .try_deserialize::<KustodioConfiguration>()?)
+            .build()
+            .and_then(|config| config.try_deserialize::<KustodioConfiguration>())
+            .map_err(|err| Error::Config(err.to_string()))
     }
 }
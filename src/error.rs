@@ -0,0 +1,105 @@
+use crate::proto::{api::lock_response::Body, LockResponse};
+use prost::Message;
+use std::fmt;
+use tonic::{metadata::MetadataValue, Code, Status};
+
+/// Metadata key carrying `Error::kind` on every failed gRPC call.
+pub const ERROR_KIND_KEY: &str = "kustodio-error";
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound(String),
+    AlreadyExists(String),
+    Storage(String),
+    AlreadyLocked,
+    NotLocked,
+    NotOwner(String),
+    PermissionDenied(String),
+    Deadlock(String),
+    Swarm(String),
+    Config(String),
+    InvalidArgument(String),
+    OutOfRange(String),
+    /// Address of the leader, empty while none is elected.
+    NotLeader(String),
+    Internal(String),
+}
+
+impl Error {
+    /// Stable machine readable name of the error kind.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::NotFound(_) => "not-found",
+            Error::AlreadyExists(_) => "already-exists",
+            Error::Storage(_) => "storage",
+            Error::AlreadyLocked => "already-locked",
+            Error::NotLocked => "not-locked",
+            Error::NotOwner(_) => "not-owner",
+            Error::PermissionDenied(_) => "permission-denied",
+            Error::Deadlock(_) => "deadlock",
+            Error::Swarm(_) => "swarm",
+            Error::Config(_) => "config",
+            Error::InvalidArgument(_) => "invalid-argument",
+            Error::OutOfRange(_) => "out-of-range",
+            Error::NotLeader(_) => "not-leader",
+            Error::Internal(_) => "internal",
+        }
+    }
+
+    fn code(&self) -> Code {
+        match self {
+            Error::NotFound(_) => Code::NotFound,
+            Error::AlreadyExists(_) => Code::AlreadyExists,
+            Error::AlreadyLocked | Error::NotLocked => Code::FailedPrecondition,
+            Error::NotOwner(_) | Error::PermissionDenied(_) => Code::PermissionDenied,
+            Error::Deadlock(_) => Code::Aborted,
+            Error::Swarm(_) | Error::NotLeader(_) => Code::Unavailable,
+            Error::InvalidArgument(_) => Code::InvalidArgument,
+            Error::OutOfRange(_) => Code::OutOfRange,
+            Error::Storage(_) | Error::Config(_) | Error::Internal(_) => Code::Internal,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotFound(key) => write!(f, "Key {} not found", key),
+            Error::AlreadyExists(key) => write!(f, "Key {} already exists", key),
+            Error::Storage(err) => write!(f, "Storage error: {}", err),
+            Error::AlreadyLocked => write!(f, "Already locked"),
+            Error::NotLocked => write!(f, "Not locked"),
+            Error::NotOwner(holder) => write!(f, "Not owner, lock is held by {:?}", holder),
+            Error::PermissionDenied(err) => write!(f, "Permission denied: {}", err),
+            Error::Deadlock(cycle) => write!(f, "Deadlock: {}", cycle),
+            Error::Swarm(err) => write!(f, "Swarm error: {}", err),
+            Error::Config(err) => write!(f, "Config error: {}", err),
+            Error::InvalidArgument(err) => write!(f, "Invalid argument: {}", err),
+            Error::OutOfRange(err) => write!(f, "Out of range: {}", err),
+            Error::NotLeader(leader) if leader.is_empty() => {
+                write!(f, "Not the leader, no leader is elected yet")
+            }
+            Error::NotLeader(leader) => write!(f, "Not the leader, the leader is {}", leader),
+            Error::Internal(err) => write!(f, "Internal error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for Status {
+    /// Maps the error to its gRPC code, tags it with its kind and carries a
+    /// `LockResponse` with the error body in the status details.
+    fn from(err: Error) -> Self {
+        let response = LockResponse {
+            body: Some(Body::Error(err.to_string())),
+            ..Default::default()
+        };
+        let mut status =
+            Status::with_details(err.code(), err.to_string(), response.encode_to_vec().into());
+        status
+            .metadata_mut()
+            .insert(ERROR_KIND_KEY, MetadataValue::from_static(err.kind()));
+        status
+    }
+}
@@ -1,7 +1,106 @@
+use crate::lock::namespace;
+use crate::proto::Status;
+
+/// A waiter aborted to break a cycle in the wait-for graph.
+#[derive(Clone, Debug)]
+pub struct Deadlock {
+    /// Lock the aborted waiter was waiting for.
+    pub name: String,
+    pub holder: String,
+    pub description: String,
+}
+
+/// A holder that lost a lock to an earlier acquisition on another node.
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    pub name: String,
+    /// Holder that lost the lock.
+    pub holder: String,
+    pub winner: String,
+}
+
 #[derive(Clone, Debug)]
 pub enum Event {
     Created(String),
     Removed(String),
     Unlocked(String),
     Locked(String),
+    Expired(String),
+    /// Someone lined up in the wait queue.
+    Queued(String),
+    /// Someone gave up waiting.
+    Dequeued(String),
+    Deadlock(Deadlock),
+    /// A participant arrived at a barrier.
+    Entered(String),
+    /// Enough participants arrived and the barrier let them through.
+    Tripped(String),
+    CountedDown(String),
+    /// A latch counted down to zero.
+    Opened(String),
+    Lost(Conflict),
+}
+
+/// An event with the revision the handler stamped it with, revisions
+/// increase by one with every event.
+#[derive(Clone, Debug)]
+pub struct Stamped {
+    pub revision: u64,
+    pub event: Event,
+}
+
+impl Event {
+    /// Lock, barrier or latch the event is about.
+    pub fn name(&self) -> &str {
+        match self {
+            Event::Deadlock(deadlock) => &deadlock.name,
+            Event::Lost(conflict) => &conflict.name,
+            Event::Created(name)
+            | Event::Removed(name)
+            | Event::Unlocked(name)
+            | Event::Locked(name)
+            | Event::Expired(name)
+            | Event::Queued(name)
+            | Event::Dequeued(name)
+            | Event::Entered(name)
+            | Event::Tripped(name)
+            | Event::CountedDown(name)
+            | Event::Opened(name) => name,
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        match self {
+            Event::Created(_) => Status::Created,
+            Event::Removed(_) => Status::Removed,
+            Event::Unlocked(_) => Status::Unlocked,
+            Event::Locked(_) => Status::Locked,
+            Event::Expired(_) => Status::Expired,
+            Event::Queued(_) => Status::Queued,
+            Event::Dequeued(_) => Status::Dequeued,
+            Event::Deadlock(_) => Status::Deadlock,
+            Event::Entered(_) => Status::Entered,
+            Event::Tripped(_) => Status::Tripped,
+            Event::CountedDown(_) => Status::CountedDown,
+            Event::Opened(_) => Status::Opened,
+            Event::Lost(_) => Status::Lost,
+        }
+    }
+}
+
+/// Events a watcher is interested in, empty fields let every event through.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    pub name: String,
+    /// Namespace the events have to be in.
+    pub prefix: String,
+    pub statuses: Vec<Status>,
+}
+
+impl Filter {
+    pub fn matches(&self, event: &Event) -> bool {
+        (self.name.is_empty() || self.name == event.name())
+            && namespace::contains(&self.prefix, event.name())
+            && (self.statuses.is_empty() || self.statuses.contains(&event.status()))
+    }
 }
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+/// Edge of the wait-for graph: `waiter` waits for `holder` to let go of `lock`.
+#[derive(Clone, Debug)]
+pub struct Wait {
+    pub waiter: String,
+    pub holder: String,
+    pub lock: String,
+    pub since: SystemTime,
+}
+
+#[derive(Default)]
+pub struct WaitForGraph {
+    waits: Vec<Wait>,
+}
+
+impl WaitForGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn wait(&mut self, wait: Wait) {
+        self.waits.push(wait);
+    }
+
+    /// Drops every edge of `waiter` waiting for `lock`.
+    pub fn remove(&mut self, waiter: &str, lock: &str) {
+        self.waits
+            .retain(|wait| wait.waiter != waiter || wait.lock != lock);
+    }
+
+    /// Finds a cycle and returns its edges in order.
+    pub fn cycle(&self) -> Option<Vec<Wait>> {
+        let mut done = HashSet::new();
+        for wait in self.waits.iter() {
+            let mut path = vec![];
+            if let Some(cycle) = self.visit(&wait.waiter, &mut path, &mut done) {
+                return Some(cycle.into_iter().cloned().collect());
+            }
+        }
+        None
+    }
+
+    fn visit<'a>(
+        &'a self,
+        node: &'a str,
+        path: &mut Vec<&'a Wait>,
+        done: &mut HashSet<&'a str>,
+    ) -> Option<Vec<&'a Wait>> {
+        if let Some(start) = path.iter().position(|wait| wait.waiter == node) {
+            return Some(path[start..].to_vec());
+        }
+        if done.contains(node) {
+            return None;
+        }
+        for wait in self.waits.iter().filter(|wait| wait.waiter == node) {
+            path.push(wait);
+            if let Some(cycle) = self.visit(&wait.holder, path, done) {
+                return Some(cycle);
+            }
+            path.pop();
+        }
+        done.insert(node);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait(graph: &mut WaitForGraph, waiter: &str, holder: &str, lock: &str) {
+        graph.wait(Wait {
+            waiter: waiter.to_string(),
+            holder: holder.to_string(),
+            lock: lock.to_string(),
+            since: SystemTime::UNIX_EPOCH,
+        });
+    }
+
+    fn locks(cycle: Vec<Wait>) -> Vec<String> {
+        cycle.into_iter().map(|wait| wait.lock).collect()
+    }
+
+    #[test]
+    fn chain_is_no_cycle() {
+        let mut graph = WaitForGraph::new();
+        wait(&mut graph, "a", "b", "x");
+        wait(&mut graph, "b", "c", "y");
+        wait(&mut graph, "d", "c", "y");
+        assert!(graph.cycle().is_none());
+    }
+
+    #[test]
+    fn finds_a_cycle_in_order() {
+        let mut graph = WaitForGraph::new();
+        wait(&mut graph, "d", "a", "w");
+        wait(&mut graph, "a", "b", "x");
+        wait(&mut graph, "b", "c", "y");
+        wait(&mut graph, "c", "a", "z");
+        // The waiter leading into the cycle is not part of it.
+        assert_eq!(locks(graph.cycle().unwrap()), vec!["x", "y", "z"]);
+    }
+
+    #[test]
+    fn waiting_for_oneself_is_a_cycle() {
+        let mut graph = WaitForGraph::new();
+        wait(&mut graph, "a", "a", "x");
+        assert_eq!(locks(graph.cycle().unwrap()), vec!["x"]);
+    }
+
+    #[test]
+    fn removing_an_edge_breaks_the_cycle() {
+        let mut graph = WaitForGraph::new();
+        wait(&mut graph, "a", "b", "x");
+        wait(&mut graph, "b", "a", "y");
+        wait(&mut graph, "b", "a", "z");
+        graph.remove("b", "y");
+        assert_eq!(locks(graph.cycle().unwrap()), vec!["x", "z"]);
+        graph.remove("b", "z");
+        assert!(graph.cycle().is_none());
+    }
+}
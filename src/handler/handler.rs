@@ -1,12 +1,23 @@
-use super::event::Event;
-use crate::lock::lock::Lock;
+use super::event::{Conflict, Deadlock, Event, Filter, Stamped};
+use super::graph::{Wait, WaitForGraph};
+use super::history::History;
+use super::seen::Seen;
+use crate::error::Error;
+use crate::lock::barrier::Barrier;
+use crate::lock::latch::Latch;
+use crate::lock::lock::{Lock, Metadata, Mode, Stamp};
+use crate::lock::namespace;
 use crate::proto::swarm;
 use crate::storage::traits::Storage;
+use crate::util::{millis_from_time, time_from_millis};
 use gossip::{Update, UpdateHandler};
 use prost::Message;
-use std::sync::{Arc, Mutex};
-use tokio::runtime::Handle;
-use tokio::sync::{mpsc, mpsc::Sender};
+use std::collections::{HashMap, HashSet};
+use std::ops::Bound;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::mpsc::{self, error::TrySendError, Sender};
 use tokio_stream::wrappers::ReceiverStream;
 
 macro_rules! ok_or_log {
@@ -21,159 +32,1898 @@ macro_rules! ok_or_log {
     };
 }
 
+/// Events kept for watchers resuming from an earlier revision.
+const HISTORY_SIZE: usize = 1000;
+/// Ids of swarm messages remembered to drop repeated deliveries.
+const SEEN_SIZE: usize = 100_000;
+/// How long a swarm message id is remembered.
+const SEEN_WINDOW: Duration = Duration::from_secs(300);
+
+/// What a committed raft entry came to, handed back to the call that
+/// proposed it.
+#[derive(Debug)]
+pub enum Applied {
+    Done,
+    /// Fencing token of an acquisition.
+    Token(u64),
+    /// Fencing tokens of a batch, in request order.
+    Tokens(Vec<u64>),
+    /// Generation a participant entered and whether the barrier tripped.
+    Entered(u64, bool),
+    /// What is left of a latch.
+    Count(u32),
+}
+
+/// A watch subscription and the events it wants.
+struct Watcher {
+    filter: Filter,
+    sender: Sender<Stamped>,
+}
+
 #[derive(Clone)]
 pub struct Handler<Store: Clone> {
     storage: Store,
-    sender: Arc<Mutex<Vec<Sender<Event>>>>,
+    barriers: Arc<Mutex<HashMap<String, Barrier>>>,
+    latches: Arc<Mutex<HashMap<String, Latch>>>,
+    sender: Arc<Mutex<Vec<Watcher>>>,
+    history: Arc<Mutex<History>>,
+    /// Held by an acquisition from checking the locks above and below it
+    /// until it took its own, so two in one namespace never both pass.
+    acquiring: Arc<Mutex<()>>,
+    /// Lamport clock, ahead of every clock seen on a message.
+    clock: Arc<AtomicU64>,
+    /// Cluster address of this node, breaks ties between equal clocks.
+    node: String,
+    /// When this node started, the longer running of two peers that
+    /// disagree on a lock wins during state sync.
+    started: SystemTime,
+    /// Encoded `SwarmMessage`s to gossip, acknowledgements of acquisitions
+    /// other nodes asked for.
+    outbox: mpsc::UnboundedSender<Vec<u8>>,
+    /// Acknowledgements awaited for acquisitions gossiped from here, by
+    /// message id.
+    acks: Arc<Mutex<HashMap<String, std::sync::mpsc::Sender<swarm::Ack>>>>,
+    /// Ids of the swarm messages sent or applied lately.
+    seen: Arc<Mutex<Seen>>,
+    /// Swarm messages dropped because they were seen already.
+    duplicates: Arc<AtomicU64>,
 }
 
 impl<Store> Handler<Store>
 where
     Store: Storage<String, Lock> + Clone,
 {
-    pub fn new(storage: Store) -> Self {
+    pub fn new(storage: Store, node: String, outbox: mpsc::UnboundedSender<Vec<u8>>) -> Self {
         Handler {
             storage: storage.clone(),
+            barriers: Arc::new(Mutex::new(HashMap::new())),
+            latches: Arc::new(Mutex::new(HashMap::new())),
             sender: Arc::new(Mutex::new(vec![])),
+            history: Arc::new(Mutex::new(History::new(HISTORY_SIZE))),
+            acquiring: Arc::new(Mutex::new(())),
+            clock: Arc::new(AtomicU64::new(0)),
+            node,
+            started: SystemTime::now(),
+            outbox,
+            acks: Arc::new(Mutex::new(HashMap::new())),
+            seen: Arc::new(Mutex::new(Seen::new(SEEN_SIZE, SEEN_WINDOW))),
+            duplicates: Arc::new(AtomicU64::new(0)),
         }
     }
 
-    pub fn created(&self, name: String) {
+    /// Creates a lock, or a semaphore if `permits` is not 0.
+    pub fn created(&self, name: String, permits: u32, reentrant: bool) -> Result<(), Error> {
         debug!("creating lock with name: {}", name);
-        if self.storage.probe(name.clone()) {
-            debug!("lock with name {} already exists", name);
-        }
-        match self.storage.set(name.clone(), Lock::new()) {
-            Ok(_) => {
-                self.send(Event::Created(name.clone()));
-                debug!("Lock with name {} created", name)
+        let lock = match (permits, reentrant) {
+            (0, false) => Lock::new(),
+            (0, true) => Lock::reentrant(),
+            (permits, false) => Lock::semaphore(permits),
+            (_, true) => {
+                return Err(Error::InvalidArgument(String::from(
+                    "Semaphores cannot be reentrant",
+                )))
             }
-            Err(err) => warn!("Could not create lock with name {}: {}", name, err),
         };
+        self.storage.insert(name.clone(), lock)?;
+        self.send(Event::Created(name.clone()));
+        debug!("Lock with name {} created", name);
+        Ok(())
     }
 
-    pub fn removed(&self, name: String) {
+    pub fn removed(&self, name: String) -> Result<(), Error> {
         debug!("Removing lock with name: {}", name);
-        match self.storage.remove(name.clone()) {
-            Ok(_) => {
-                self.send(Event::Removed(name.clone()));
-                debug!("removed lock with name {}", name)
-            }
-            Err(err) => debug!("could not remove lock with name {}: {}", name, err),
-        }
-    }
-
-    pub fn list(&self) -> Result<Vec<(String, Lock)>, anyhow::Error> {
-        Ok(self.storage.list()?)
-    }
-
-    pub fn locked(&self, name: String) {
-        debug!("Locking {}", name);
-        match self.storage.get(name.clone()) {
-            Ok(lock) => match lock.clone().locked() {
-                true => debug!("Nothing to do. {} locked", name),
-                false => {
-                    debug!("Trying lock {}", name);
-                    let mut nu_lock = lock.clone();
-                    match nu_lock.lock() {
-                        Some(err) => debug!("Error locking {}: {}", name, err),
-                        None => debug!("Locked {}", name),
-                    };
-                    match self.storage.set(name.clone(), nu_lock.to_owned()) {
-                        Ok(_) => debug!("Saved lock {}", name),
-                        Err(err) => debug!("Could not lock {}: {}", name, err),
+        self.storage.remove(name.clone())?;
+        self.send(Event::Removed(name.clone()));
+        debug!("removed lock with name {}", name);
+        Ok(())
+    }
+
+    /// Removes every lock in `namespace` and returns their names.
+    pub fn removed_all(&self, namespace: String) -> Result<Vec<String>, Error> {
+        if namespace.trim_end_matches(namespace::SEPARATOR).is_empty() {
+            return Err(Error::InvalidArgument(String::from(
+                "A namespace is required",
+            )));
+        }
+        debug!("Removing locks in {}", namespace);
+        let mut removed = vec![];
+        for (name, _) in self.list(&namespace)? {
+            self.storage.remove(name.clone())?;
+            self.send(Event::Removed(name.clone()));
+            removed.push(name);
+        }
+        if removed.is_empty() {
+            return Err(Error::NotFound(namespace));
+        }
+        debug!("Removed {:?}", removed);
+        Ok(removed)
+    }
+
+    /// Locks in `namespace`, all of them for the empty one.
+    pub fn list(&self, namespace: &str) -> Result<Vec<(String, Lock)>, Error> {
+        Ok(self.list_page(namespace, None, None, usize::MAX)?.0)
+    }
+
+    /// Up to `limit` locks in `namespace` in name order, beginning after the
+    /// name `after`. With `state` only locked or unlocked ones are returned.
+    /// Also tells whether more locks follow the page.
+    pub fn list_page(
+        &self,
+        namespace: &str,
+        state: Option<bool>,
+        after: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<(String, Lock)>, bool), Error> {
+        // Names in a namespace all start with it, the scan begins there and
+        // ends at the first name that does not.
+        let prefix = namespace.trim_end_matches(namespace::SEPARATOR);
+        let from = match after {
+            Some(after) if after.as_str() >= prefix => Bound::Excluded(after),
+            _ => Bound::Included(prefix.to_string()),
+        };
+        let mut page = vec![];
+        let mut more = false;
+        self.storage.scan(from, &mut |name, lock| {
+            if !name.starts_with(prefix) {
+                return false;
+            }
+            if !namespace::contains(prefix, name)
+                || matches!(state, Some(locked) if locked != lock.locked())
+            {
+                return true;
+            }
+            if page.len() == limit {
+                more = true;
+                return false;
+            }
+            page.push((name.clone(), lock.clone()));
+            true
+        })?;
+        Ok((page, more))
+    }
+
+    /// Advances the Lamport clock for a change made on this node.
+    pub fn tick(&self) -> Stamp {
+        Stamp {
+            clock: self.clock.fetch_add(1, Ordering::SeqCst) + 1,
+            node: self.node.clone(),
+        }
+    }
+
+    /// Moves the Lamport clock up to `clock` seen on a message, so the
+    /// next local change comes after it.
+    fn witness(&self, clock: u64) {
+        self.clock.fetch_max(clock, Ordering::SeqCst);
+    }
+
+    /// Acquires the lock for `holder` in `mode`, or `permits` permits of a
+    /// semaphore, at `stamp` and returns its fencing token.
+    pub fn locked(
+        &self,
+        name: String,
+        holder: String,
+        mode: Mode,
+        permits: u32,
+        expires_at: Option<SystemTime>,
+        stamp: Stamp,
+    ) -> Result<u64, Error> {
+        debug!("Locking {} for {:?} ({:?})", name, holder, mode);
+        let _acquiring = self.acquiring()?;
+        self.guard(&name, &holder)?;
+        let token = self.storage.update(name.clone(), |lock| {
+            lock.in_turn(&holder)?;
+            lock.lock(holder.clone(), mode, permits, expires_at, 0)?;
+            lock.stamped(&holder, stamp);
+            Ok(lock.token())
+        })?;
+        debug!("Locked {} with token {}", name, token);
+        self.send(Event::Locked(name));
+        Ok(token)
+    }
+
+    /// Acquires the lock exclusively for `holder` together with every lock
+    /// below it, which fails while any of them is held by someone else.
+    pub fn locked_subtree(
+        &self,
+        name: String,
+        holder: String,
+        expires_at: Option<SystemTime>,
+        stamp: Stamp,
+    ) -> Result<u64, Error> {
+        debug!("Locking {} and below for {:?}", name, holder);
+        let _acquiring = self.acquiring()?;
+        self.guard(&name, &holder)?;
+        self.guard_below(&name, &holder)?;
+        let token = self.storage.update(name.clone(), |lock| {
+            lock.in_turn(&holder)?;
+            lock.lock_subtree(holder.clone(), expires_at, 0)?;
+            lock.stamped(&holder, stamp);
+            Ok(lock.token())
+        })?;
+        debug!("Locked {} and below with token {}", name, token);
+        self.send(Event::Locked(name));
+        Ok(token)
+    }
+
+    /// Applies an acquisition gossiped by another node. If it conflicts with
+    /// the current holders, every node lets the earlier acquisitions keep the
+    /// lock, see `Lock::contest`, and tells the holders that lost. Returns
+    /// whether the lock went to the holder of `msg`.
+    fn claimed(&self, msg: swarm::LockMessage) -> Result<bool, Error> {
+        let name = msg.name.clone();
+        let _acquiring = self.acquiring()?;
+        self.guard(&name, &msg.holder)?;
+        if msg.subtree {
+            self.guard_below(&name, &msg.holder)?;
+        }
+        let contest = self
+            .storage
+            .update(name.clone(), |lock| settle(lock, &msg))?;
+        let losers = match contest {
+            Ok(losers) => losers,
+            Err(winner) => {
+                debug!("{:?} keeps {}, {:?} came later", winner, name, msg.holder);
+                self.send(Event::Lost(Conflict {
+                    name,
+                    holder: msg.holder,
+                    winner,
+                }));
+                return Ok(false);
+            }
+        };
+        self.settled(&msg, losers);
+        Ok(true)
+    }
+
+    /// Applies a batch of acquisitions gossiped by another node, all of them
+    /// or none. Conflicts are settled like for `claimed`, and if any lock of
+    /// the batch stays with its current holders the batch loses all of them.
+    /// Returns whether the locks went to the holder of the batch.
+    fn claimed_batch(&self, batch: swarm::LockBatch) -> Result<bool, Error> {
+        let names: Vec<String> = batch.locks.iter().map(|msg| msg.name.clone()).collect();
+        let _acquiring = self.acquiring()?;
+        for msg in batch.locks.iter() {
+            self.guard(&msg.name, &msg.holder)?;
+        }
+        let mut lost = None;
+        let settled = self.storage.update_all(&names, |locks| {
+            let mut losers = vec![];
+            for (lock, msg) in locks.iter_mut().zip(batch.locks.iter()) {
+                match settle(lock, msg)? {
+                    Ok(evicted) => losers.push(evicted),
+                    Err(winner) => {
+                        lost = Some(Conflict {
+                            name: msg.name.clone(),
+                            holder: msg.holder.clone(),
+                            winner,
+                        });
+                        // Leaves every lock of the batch as it was.
+                        return Err(Error::AlreadyLocked);
                     }
-                    self.send(Event::Locked(name.clone()));
                 }
+            }
+            Ok(losers)
+        });
+        let losers = match (settled, lost) {
+            (Ok(losers), _) => losers,
+            (Err(_), Some(lost)) => {
+                debug!(
+                    "{:?} keeps {}, {:?} came later with {:?}",
+                    lost.winner, lost.name, lost.holder, names
+                );
+                self.send(Event::Lost(lost));
+                return Ok(false);
+            }
+            (Err(err), None) => return Err(err),
+        };
+        for (msg, losers) in batch.locks.iter().zip(losers) {
+            self.settled(msg, losers);
+        }
+        Ok(true)
+    }
+
+    /// Tells the `losers` of a conflict `msg` won and everyone that it holds
+    /// the lock now.
+    fn settled(&self, msg: &swarm::LockMessage, losers: Vec<String>) {
+        for loser in losers {
+            debug!("{:?} takes {} from {:?}", msg.holder, msg.name, loser);
+            self.send(Event::Lost(Conflict {
+                name: msg.name.clone(),
+                holder: loser,
+                winner: msg.holder.clone(),
+            }));
+        }
+        self.send(Event::Locked(msg.name.clone()));
+    }
+
+    /// Starts collecting the acknowledgements for the acquisition gossiped
+    /// as `message_id`.
+    pub fn expect_acks(
+        &self,
+        message_id: String,
+    ) -> Result<std::sync::mpsc::Receiver<swarm::Ack>, Error> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.acks
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .insert(message_id, tx);
+        Ok(rx)
+    }
+
+    pub fn forget_acks(&self, message_id: &str) -> Result<(), Error> {
+        self.acks
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .remove(message_id);
+        Ok(())
+    }
+
+    /// Hands an acknowledgement to whoever waits for it, others are dropped.
+    fn acked(&self, ack: swarm::Ack) -> Result<(), Error> {
+        if ack.node == self.node {
+            return Ok(());
+        }
+        let acks = self
+            .acks
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        if let Some(sender) = acks.get(&ack.message_id) {
+            let _ = sender.send(ack);
+        }
+        Ok(())
+    }
+
+    /// Tells the node that gossiped the acquisition `message_id` whether the
+    /// lock went to its holder here as well.
+    fn acknowledge(&self, message_id: String, claimed: &Result<bool, Error>) -> Result<(), Error> {
+        let ack = swarm::Ack {
+            message_id,
+            node: self.node.clone(),
+            accepted: matches!(claimed, Ok(true)),
+            reason: match claimed {
+                Ok(true) => String::new(),
+                Ok(false) => String::from("Held by an earlier acquisition"),
+                Err(err) => err.to_string(),
             },
-            Err(err) => debug!("Could not lock {}: {}", name, err),
-        };
-    }
-
-    pub fn unlocked(&self, name: String) {
-        debug!("Unlocking {}", name);
-        match self.storage.get(name.clone()) {
-            Ok(lock) => match lock.clone().locked() {
-                false => debug!("Nothing to do. {} unlocked", name),
-                true => {
-                    let mut nu_lock = lock.clone();
-                    match nu_lock.unlock() {
-                        Some(err) => debug!("Error unlocking {}: {}", name, err),
-                        None => {}
-                    };
-                    match self.storage.set(name.clone(), nu_lock.to_owned()) {
-                        Ok(_) => debug!("Unlocked {}", name),
-                        Err(err) => debug!("Could not unlock {}: {}", name, err),
-                    };
-                    self.send(Event::Unlocked(name.clone()));
+        };
+        self.gossip(swarm::swarm_message::Payload::Ack(ack))
+    }
+
+    /// Records the id of a swarm message sent or received, false if it was
+    /// seen already. Messages without an id always count as new.
+    pub fn remember(&self, message_id: &str) -> Result<bool, Error> {
+        if message_id.is_empty() {
+            return Ok(true);
+        }
+        Ok(self
+            .seen
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .insert(message_id, Instant::now()))
+    }
+
+    /// Swarm messages dropped so far because they were seen already.
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates.load(Ordering::SeqCst)
+    }
+
+    /// Queues `payload` up for the swarm.
+    fn gossip(&self, payload: swarm::swarm_message::Payload) -> Result<(), Error> {
+        if let Some(message_id) = payload.message_id() {
+            self.remember(message_id)?;
+        }
+        let msg = swarm::SwarmMessage {
+            payload: Some(payload),
+        };
+        self.outbox
+            .send(msg.encode_to_vec())
+            .map_err(|err| Error::Swarm(err.to_string()))
+    }
+
+    /// Gossips a summary of all locks here, so peers can pull those they
+    /// miss or disagree on.
+    pub fn digest(&self) -> Result<(), Error> {
+        let mut locks = HashMap::new();
+        self.storage.scan(Bound::Unbounded, &mut |name, lock| {
+            let summary = swarm::Summary {
+                token: lock.token(),
+                fingerprint: lock.fingerprint(),
+            };
+            locks.insert(name.clone(), summary);
+            true
+        })?;
+        let barriers = self
+            .barriers
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .iter()
+            .map(|(name, barrier)| {
+                let summary = swarm::BarrierSummary {
+                    generation: barrier.generation(),
+                    fingerprint: barrier.fingerprint(),
+                };
+                (name.clone(), summary)
+            })
+            .collect();
+        let latches = self
+            .latches
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .iter()
+            .map(|(name, latch)| (name.clone(), latch.count()))
+            .collect();
+        self.gossip(swarm::swarm_message::Payload::Digest(swarm::Digest {
+            node: self.node.clone(),
+            locks,
+            message_id: nano_id::base64::<21>(),
+            started: millis_from_time(Some(self.started)),
+            barriers,
+            latches,
+        }))
+    }
+
+    /// Pulls the locks of a peer's digest that are missing here or behind
+    /// the peer's fencing token, and likewise barriers behind its generation
+    /// and latches it counted down further. Peers that disagree on a lock or
+    /// barrier at the same token or generation take it from the one that
+    /// runs longer, a restarted node may have applied the gossip it caught up
+    /// on out of order.
+    fn compared(&self, digest: swarm::Digest) -> Result<(), Error> {
+        if digest.node == self.node {
+            return Ok(());
+        }
+        let elder =
+            (millis_from_time(Some(self.started)), &self.node) < (digest.started, &digest.node);
+        let mut names = vec![];
+        for (name, summary) in digest.locks {
+            match self.storage.get(name.clone()) {
+                Ok(lock) if lock.token() > summary.token => {}
+                Ok(lock)
+                    if lock.token() == summary.token
+                        && (lock.fingerprint() == summary.fingerprint || elder) => {}
+                Ok(_) | Err(Error::NotFound(_)) => names.push(name),
+                Err(err) => return Err(err),
+            }
+        }
+        names.sort();
+        let mut barriers = {
+            let known = self
+                .barriers
+                .lock()
+                .map_err(|err| Error::Internal(err.to_string()))?;
+            digest
+                .barriers
+                .into_iter()
+                .filter(|(name, summary)| match known.get(name) {
+                    Some(barrier) if barrier.generation() > summary.generation => false,
+                    Some(barrier) if barrier.generation() == summary.generation => {
+                        barrier.fingerprint() != summary.fingerprint && !elder
+                    }
+                    _ => true,
+                })
+                .map(|(name, _)| name)
+                .collect::<Vec<String>>()
+        };
+        barriers.sort();
+        let mut latches = {
+            let known = self
+                .latches
+                .lock()
+                .map_err(|err| Error::Internal(err.to_string()))?;
+            digest
+                .latches
+                .into_iter()
+                .filter(|(name, count)| match known.get(name) {
+                    Some(latch) => latch.count() > *count,
+                    None => true,
+                })
+                .map(|(name, _)| name)
+                .collect::<Vec<String>>()
+        };
+        latches.sort();
+        if names.is_empty() && barriers.is_empty() && latches.is_empty() {
+            return Ok(());
+        }
+        debug!(
+            "Pulling {:?}, barriers {:?} and latches {:?} from {}",
+            names, barriers, latches, digest.node
+        );
+        self.gossip(swarm::swarm_message::Payload::Pull(swarm::Pull {
+            node: self.node.clone(),
+            from: digest.node,
+            names,
+            message_id: nano_id::base64::<21>(),
+            barriers,
+            latches,
+        }))
+    }
+
+    /// Answers a pull asking this node with the locks it asks for.
+    fn pulled(&self, pull: swarm::Pull) -> Result<(), Error> {
+        if pull.from != self.node {
+            return Ok(());
+        }
+        let snapshot = self.captured(pull.names, pull.barriers, pull.latches)?;
+        self.gossip(swarm::swarm_message::Payload::Snapshot(swarm::Snapshot {
+            node: pull.node,
+            ..snapshot
+        }))
+    }
+
+    /// Encoded `Snapshot` of every lock, barrier and latch, see `reset`.
+    pub fn dump(&self) -> Result<Vec<u8>, Error> {
+        let names = self.storage.list()?.into_iter().map(|(name, _)| name);
+        let barriers = self
+            .barriers
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .keys()
+            .cloned()
+            .collect();
+        let latches = self
+            .latches
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .keys()
+            .cloned()
+            .collect();
+        let mut buffer = vec![];
+        self.captured(names.collect(), barriers, latches)?
+            .encode(&mut buffer)
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        Ok(buffer)
+    }
+
+    /// Drops every lock, barrier and latch and recreates those of a `dump`.
+    pub fn reset(&self, dump: &[u8]) -> Result<(), Error> {
+        let snapshot =
+            swarm::Snapshot::decode(dump).map_err(|err| Error::Internal(err.to_string()))?;
+        for (name, _) in self.storage.list()? {
+            self.removed(name)?;
+        }
+        self.barriers
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .clear();
+        self.latches
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .clear();
+        self.restored(swarm::Snapshot {
+            node: self.node.clone(),
+            ..snapshot
+        })
+    }
+
+    /// Snapshot of the locks in `names` and the barriers and latches named,
+    /// addressed to nobody yet.
+    fn captured(
+        &self,
+        names: Vec<String>,
+        barrier_names: Vec<String>,
+        latch_names: Vec<String>,
+    ) -> Result<swarm::Snapshot, Error> {
+        let mut messages = vec![];
+        for name in names {
+            match self.snapshot(name) {
+                Ok(snapshot) => messages.extend(snapshot),
+                Err(Error::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        let mut barriers = vec![];
+        for name in barrier_names {
+            let barrier = match self.barrier(name.clone()) {
+                Ok(barrier) => barrier,
+                Err(Error::NotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            barriers.push(swarm::BarrierMessage {
+                name: name.clone(),
+                action: swarm::barrier_message::Action::Created.into(),
+                message_id: nano_id::base64::<21>(),
+                parties: barrier.parties(),
+                generation: barrier.generation(),
+                ..Default::default()
+            });
+            for participant in barrier.participants() {
+                barriers.push(swarm::BarrierMessage {
+                    name: name.clone(),
+                    action: swarm::barrier_message::Action::Entered.into(),
+                    message_id: nano_id::base64::<21>(),
+                    participant,
+                    generation: barrier.generation(),
+                    ..Default::default()
+                });
+            }
+        }
+        let mut latches = vec![];
+        for name in latch_names {
+            match self.latch(name.clone()) {
+                Ok(latch) => latches.push(swarm::LatchMessage {
+                    name,
+                    action: swarm::latch_message::Action::Created.into(),
+                    message_id: nano_id::base64::<21>(),
+                    count: latch.count(),
+                }),
+                Err(Error::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(swarm::Snapshot {
+            node: String::new(),
+            messages,
+            message_id: nano_id::base64::<21>(),
+            barriers,
+            latches,
+        })
+    }
+
+    /// Messages that recreate the lock `name` as it is here.
+    fn snapshot(&self, name: String) -> Result<Vec<swarm::LockMessage>, Error> {
+        let lock = self.storage.get(name.clone())?;
+        let mut messages = vec![swarm::LockMessage {
+            name: name.clone(),
+            action: swarm::lock_message::Action::Created.into(),
+            message_id: nano_id::base64::<21>(),
+            permits: lock.permits(),
+            reentrant: lock.is_reentrant(),
+            metadata: Some(lock.annotations().clone().into()),
+            token: lock.token(),
+            ..Default::default()
+        }];
+        let mode = match lock.shared() {
+            true => swarm::lock_message::Mode::Shared,
+            false => swarm::lock_message::Mode::Exclusive,
+        };
+        let holders = lock.holders();
+        for (index, holder) in holders.iter().enumerate() {
+            let stamp = lock.stamp_of(holder).cloned().unwrap_or(Stamp {
+                clock: 0,
+                node: String::new(),
+            });
+            messages.push(swarm::LockMessage {
+                name: name.clone(),
+                action: swarm::lock_message::Action::Locked.into(),
+                message_id: nano_id::base64::<21>(),
+                holder: holder.clone(),
+                expires_at: millis_from_time(lock.expires_at()),
+                // Every acquisition advances the token, the last one sets it.
+                token: match index + 1 == holders.len() {
+                    true => lock.token(),
+                    false => 0,
+                },
+                mode: mode.into(),
+                permits: lock.taken(holder),
+                subtree: lock.subtree(),
+                clock: stamp.clock,
+                node: stamp.node.clone(),
+                metadata: Some(lock.attachments().clone().into()),
+                ..Default::default()
+            });
+        }
+        for waiter in lock.waiters() {
+            messages.push(swarm::LockMessage {
+                name: name.clone(),
+                action: swarm::lock_message::Action::Queued.into(),
+                message_id: nano_id::base64::<21>(),
+                queued_at: millis_from_time(lock.waiting_since(&waiter)),
+                wait_until: millis_from_time(lock.waiting_until(&waiter)),
+                holder: waiter,
+                ..Default::default()
+            });
+        }
+        Ok(messages)
+    }
+
+    /// Recreates the locks of a snapshot meant for this node. Locks here
+    /// ahead of the snapshot's fencing token are left as they are, the
+    /// others are replaced.
+    fn restored(&self, snapshot: swarm::Snapshot) -> Result<(), Error> {
+        if snapshot.node != self.node {
+            return Ok(());
+        }
+        let mut skipped = HashSet::new();
+        // Fencing tokens to adopt once the acquisitions are replayed, which
+        // advance the token themselves.
+        let mut tokens = HashMap::new();
+        for msg in snapshot.messages {
+            let name = msg.name.clone();
+            if msg.action == swarm::lock_message::Action::Created as i32 {
+                match self.storage.get(name.clone()) {
+                    Ok(lock) if lock.token() > msg.token => {
+                        skipped.insert(name.clone());
+                    }
+                    Ok(_) => {
+                        debug!("Replacing stale lock {}", name);
+                        self.storage.remove(name.clone())?;
+                        skipped.remove(&name);
+                        tokens.insert(name.clone(), msg.token);
+                    }
+                    Err(Error::NotFound(_)) => {
+                        skipped.remove(&name);
+                        tokens.insert(name.clone(), msg.token);
+                    }
+                    Err(err) => return Err(err),
                 }
-            },
-            Err(err) => debug!("Could not unlock {}: {}", name, err),
+            }
+            if skipped.contains(&name) {
+                continue;
+            }
+            if let Err(err) = self.lock_message(msg) {
+                warn!("Could not restore {}: {}", name, err);
+                skipped.insert(name);
+            }
+        }
+        for (name, token) in tokens {
+            match self.storage.update(name, |lock| {
+                lock.fence(token);
+                Ok(())
+            }) {
+                Ok(()) | Err(Error::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        let mut skipped = HashSet::new();
+        for msg in snapshot.barriers {
+            let name = msg.name.clone();
+            if msg.action == swarm::barrier_message::Action::Created as i32 {
+                match self.barrier_restored(msg) {
+                    Ok(true) => skipped.remove(&name),
+                    Ok(false) => skipped.insert(name),
+                    Err(err) => {
+                        warn!("Could not restore barrier {}: {}", name, err);
+                        skipped.insert(name)
+                    }
+                };
+                continue;
+            }
+            if skipped.contains(&name) {
+                continue;
+            }
+            if let Err(err) = self.entered(name.clone(), msg.participant, Some(msg.generation)) {
+                warn!("Could not restore barrier {}: {}", name, err);
+            }
+        }
+        for msg in snapshot.latches {
+            self.latch_restored(msg.name, msg.count)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the barrier with the one of a snapshot unless it is ahead of
+    /// it, returns whether it did. Participants waiting for a trip the
+    /// snapshot is past are released.
+    fn barrier_restored(&self, msg: swarm::BarrierMessage) -> Result<bool, Error> {
+        let mut barrier = Barrier::new(msg.parties)?;
+        barrier.fence(msg.generation);
+        let tripped = {
+            let mut barriers = self
+                .barriers
+                .lock()
+                .map_err(|err| Error::Internal(err.to_string()))?;
+            let tripped = match barriers.get(&msg.name) {
+                Some(known) if known.generation() > msg.generation => return Ok(false),
+                Some(known) => known.generation() < msg.generation,
+                None => false,
+            };
+            debug!("Restoring barrier {}", msg.name);
+            barriers.insert(msg.name.clone(), barrier);
+            tripped
+        };
+        if tripped {
+            self.send(Event::Tripped(msg.name));
+        }
+        Ok(true)
+    }
+
+    /// Takes over the count of a latch from a snapshot if it is counted down
+    /// further than here.
+    fn latch_restored(&self, name: String, count: u32) -> Result<(), Error> {
+        let (new, opened) = {
+            let mut latches = self
+                .latches
+                .lock()
+                .map_err(|err| Error::Internal(err.to_string()))?;
+            let new = match latches.get(&name) {
+                Some(latch) if latch.count() <= count => return Ok(()),
+                Some(_) => false,
+                None => true,
+            };
+            debug!("Restoring latch {} at {}", name, count);
+            let latch = Latch::new(count);
+            let opened = latch.open();
+            latches.insert(name.clone(), latch);
+            (new, opened)
         };
+        match new {
+            true => self.send(Event::Created(name.clone())),
+            false => self.send(Event::CountedDown(name.clone())),
+        }
+        if opened {
+            self.send(Event::Opened(name));
+        }
+        Ok(())
+    }
+
+    /// Keeps other acquisitions out until the returned guard drops, see
+    /// `acquiring`.
+    fn acquiring(&self) -> Result<MutexGuard<'_, ()>, Error> {
+        self.acquiring
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))
+    }
+
+    /// Fails if a lock below `name` is held by someone else.
+    fn guard_below(&self, name: &str, holder: &str) -> Result<(), Error> {
+        for (child, lock) in self.list(name)? {
+            if child != name && lock.holders().iter().any(|other| other != holder) {
+                debug!("{} is held below {}", child, name);
+                return Err(Error::AlreadyLocked);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if a lock above `name` holds its subtree for someone else.
+    fn guard(&self, name: &str, holder: &str) -> Result<(), Error> {
+        for parent in namespace::parents(name) {
+            match self.storage.get(parent.to_string()) {
+                Ok(lock) if lock.subtree() && lock.holder().as_deref() != Some(holder) => {
+                    debug!("{} is held with everything below", parent);
+                    return Err(Error::AlreadyLocked);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Attaches `metadata` to the lock, see `Lock::annotate`.
+    pub fn annotated(&self, name: String, metadata: Metadata) -> Result<(), Error> {
+        if metadata.is_empty() {
+            return Ok(());
+        }
+        debug!("Annotating {} with {:?}", name, metadata);
+        self.storage.update(name, |lock| {
+            lock.annotate(metadata);
+            Ok(())
+        })
+    }
+
+    /// Attaches `metadata` to the lock while it is held, see `Lock::attach`.
+    pub fn attached(&self, name: String, metadata: Metadata) -> Result<(), Error> {
+        if metadata.is_empty() {
+            return Ok(());
+        }
+        debug!("Attaching {:?} to {}", metadata, name);
+        self.storage.update(name, |lock| {
+            lock.attach(metadata);
+            Ok(())
+        })
+    }
+
+    /// Takes another hold on a reentrant lock `holder` already owns and
+    /// returns its unchanged fencing token, `None` if that is not the case.
+    pub fn reentered(&self, name: String, holder: String) -> Result<Option<u64>, Error> {
+        let reentered = self.storage.update(name.clone(), |lock| {
+            Ok(lock.reenter(&holder).then(|| (lock.token(), lock.holds())))
+        })?;
+        let (token, holds) = match reentered {
+            Some(reentered) => reentered,
+            None => return Ok(None),
+        };
+        debug!("{:?} holds {} {} times", holder, name, holds);
+        Ok(Some(token))
     }
-    pub fn state(&self, name: String) -> Result<bool, anyhow::Error> {
+
+    /// Acquires every lock in `names` for `holder` at `stamp` or none of
+    /// them, and returns their fencing tokens.
+    pub fn locked_many(
+        &self,
+        holder: String,
+        expires_at: Option<SystemTime>,
+        names: Vec<String>,
+        stamp: Stamp,
+    ) -> Result<Vec<u64>, Error> {
+        debug!("Locking {:?} for {:?}", names, holder);
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) {
+                return Err(Error::InvalidArgument(format!("{} requested twice", name)));
+            }
+        }
+        let _acquiring = self.acquiring()?;
+        for name in names.iter() {
+            self.guard(name, &holder)?;
+        }
+        let tokens = self.storage.update_all(&names, |acquired| {
+            let mut tokens = vec![];
+            for lock in acquired.iter_mut() {
+                lock.in_turn(&holder)?;
+                lock.lock(holder.clone(), Mode::Exclusive, 0, expires_at, 0)?;
+                lock.stamped(&holder, stamp.clone());
+                tokens.push(lock.token());
+            }
+            Ok(tokens)
+        })?;
+        for name in names {
+            self.send(Event::Locked(name));
+        }
+        Ok(tokens)
+    }
+
+    /// Releases the lock for `holder`, or `permits` of its semaphore permits.
+    /// Fails if the lock is held by someone else and `force` is not set.
+    ///
+    /// Returns the holds `holder` keeps on a reentrant lock, which is only
+    /// released once that reaches 0.
+    pub fn unlocked(
+        &self,
+        name: String,
+        holder: String,
+        permits: u32,
+        force: bool,
+    ) -> Result<u32, Error> {
+        self.release(name, holder, permits, force, Event::Unlocked)
+    }
+
+    /// Extends the lease of a lock held by `holder` to `expires_at`.
+    pub fn renewed(
+        &self,
+        name: String,
+        holder: String,
+        expires_at: Option<SystemTime>,
+    ) -> Result<(), Error> {
+        debug!("Renewing {} for {:?}", name, holder);
+        self.storage
+            .update(name.clone(), |lock| lock.renew(&holder, expires_at))?;
+        debug!("Renewed {}", name);
+        Ok(())
+    }
+
+    /// Names and fencing tokens of the locks whose lease ran out. The node
+    /// that handed out the latest acquisition of a lock finds it at its
+    /// deadline, the others once it is `grace` overdue, in case that node is
+    /// gone.
+    pub fn overdue(&self, grace: Duration) -> Result<Vec<(String, u64)>, Error> {
+        let now = SystemTime::now();
+        let late = now.checked_sub(grace).unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut overdue = vec![];
+        self.storage.scan(Bound::Unbounded, &mut |name, lock| {
+            if lock.expired(if self.handed_out_here(lock) {
+                now
+            } else {
+                late
+            }) {
+                overdue.push((name.clone(), lock.token()));
+            }
+            true
+        })?;
+        Ok(overdue)
+    }
+
+    /// Force-releases the locks whose lease ran out, see `overdue`, gossips
+    /// their expiry and returns their names.
+    pub fn reap(&self, grace: Duration) -> Result<Vec<String>, Error> {
+        let mut expired = vec![];
+        for (name, token) in self.overdue(grace)? {
+            if !self.expired(name.clone(), token)? {
+                continue;
+            }
+            self.gossip(swarm::swarm_message::Payload::LockMessage(
+                swarm::LockMessage {
+                    name: name.clone(),
+                    action: swarm::lock_message::Action::Expired.into(),
+                    message_id: nano_id::base64::<21>(),
+                    token,
+                    ..Default::default()
+                },
+            ))?;
+            expired.push(name);
+        }
+        Ok(expired)
+    }
+
+    /// Whether this node handed out the latest acquisition of `lock`. Locks
+    /// that do not record where they were acquired count as everyone's.
+    pub fn handed_out_here(&self, lock: &Lock) -> bool {
+        match lock.stamp() {
+            Some(stamp) => stamp.node.is_empty() || stamp.node == self.node,
+            None => true,
+        }
+    }
+
+    /// Force-releases the lock if it is still held by the acquisition with
+    /// fencing token `token`, whose lease ran out. Returns whether it did.
+    pub fn expired(&self, name: String, token: u64) -> Result<bool, Error> {
+        let released = self.storage.update(name.clone(), |lock| {
+            if !lock.locked() || lock.token() != token {
+                return Ok(false);
+            }
+            lock.unlock(&lock.holder().unwrap_or_default(), 0, true)?;
+            Ok(true)
+        })?;
+        if released {
+            debug!("Lease of {} expired", name);
+            self.send(Event::Expired(name));
+        }
+        Ok(released)
+    }
+
+    fn release(
+        &self,
+        name: String,
+        holder: String,
+        permits: u32,
+        force: bool,
+        event: fn(String) -> Event,
+    ) -> Result<u32, Error> {
+        debug!("Unlocking {} for {:?}", name, holder);
+        let (holds, shared) = self.storage.update(name.clone(), |lock| {
+            lock.unlock(&holder, permits, force)?;
+            Ok((lock.holds(), lock.shared()))
+        })?;
+        if holds > 0 {
+            debug!("{:?} still holds {} {} times", holder, name, holds);
+            return Ok(holds);
+        }
+        // Semaphore waiters want to hear about every freed permit, readers
+        // only about the last one leaving.
+        if shared {
+            debug!("{:?} left {}, still shared", holder, name);
+            return Ok(0);
+        }
+        debug!("Unlocked {}", name);
+        self.send(event(name));
+        Ok(0)
+    }
+
+    /// Lines `holder`, waiting since `since` until `until` at most, up in the
+    /// wait queue and returns its place. A holder already waiting only moves
+    /// `until`.
+    pub fn queued(
+        &self,
+        name: String,
+        holder: String,
+        since: SystemTime,
+        until: Option<SystemTime>,
+    ) -> Result<usize, Error> {
+        debug!("Queueing {:?} for {}", holder, name);
+        let (position, new) = self.storage.update(name.clone(), |lock| {
+            let new = lock.position(&holder).is_none();
+            Ok((lock.enqueue(holder, since, until), new))
+        })?;
+        if new {
+            self.send(Event::Queued(name));
+        }
+        Ok(position)
+    }
+
+    /// Takes `holder` out of the wait queue, returns whether it was waiting.
+    pub fn dequeued(&self, name: String, holder: String) -> Result<bool, Error> {
+        debug!("Dequeueing {:?} from {}", holder, name);
+        if !self
+            .storage
+            .update(name.clone(), |lock| Ok(lock.dequeue(&holder)))?
+        {
+            return Ok(false);
+        }
+        self.send(Event::Dequeued(name));
+        Ok(true)
+    }
+
+    /// Locks and holders waiting for them past the time they meant to give
+    /// up, their node may be gone.
+    pub fn lapsed(&self) -> Result<Vec<(String, String)>, Error> {
+        let now = SystemTime::now();
+        let mut lapsed = vec![];
+        self.storage.scan(Bound::Unbounded, &mut |name, lock| {
+            for waiter in lock.lapsed(now) {
+                lapsed.push((name.clone(), waiter));
+            }
+            true
+        })?;
+        Ok(lapsed)
+    }
+
+    /// Takes the waiters out of the queues they meant to leave already, see
+    /// `lapsed`, and returns them. Every node knows when they give up, so
+    /// none gossips it.
+    pub fn lapse(&self) -> Result<Vec<(String, String)>, Error> {
+        let lapsed = self.lapsed()?;
+        for (name, holder) in lapsed.iter() {
+            self.dequeued(name.clone(), holder.clone())?;
+        }
+        Ok(lapsed)
+    }
+
+    /// Place of `holder` in the wait queue of `name`, 0 if it is not waiting.
+    pub fn position(&self, name: String, holder: String) -> Result<usize, Error> {
+        Ok(self
+            .storage
+            .get(name)?
+            .position(&holder)
+            .unwrap_or_default())
+    }
+
+    /// Builds the wait-for graph from lock holders and queued waiters, where a
+    /// waiter also waits for everyone ahead of it in the queue, and picks the
+    /// youngest waiter of each cycle to break it.
+    pub fn deadlocks(&self) -> Result<Vec<Deadlock>, Error> {
+        let mut graph = WaitForGraph::new();
+        self.storage.scan(Bound::Unbounded, &mut |name, lock| {
+            let waiters = lock.waiters();
+            for (index, waiter) in waiters.iter().enumerate() {
+                let since = lock.waiting_since(waiter).unwrap_or(SystemTime::UNIX_EPOCH);
+                for holder in lock.holders().iter().chain(waiters[..index].iter()) {
+                    if holder == waiter {
+                        continue;
+                    }
+                    graph.wait(Wait {
+                        waiter: waiter.clone(),
+                        holder: holder.clone(),
+                        lock: name.clone(),
+                        since,
+                    });
+                }
+            }
+            true
+        })?;
+        let mut deadlocks = vec![];
+        while let Some(cycle) = graph.cycle() {
+            let victim = match cycle.iter().max_by_key(|wait| wait.since) {
+                Some(victim) => victim.clone(),
+                None => break,
+            };
+            graph.remove(&victim.waiter, &victim.lock);
+            deadlocks.push(Deadlock {
+                description: format!(
+                    "{}; aborting {:?} waiting for {}",
+                    cycle
+                        .iter()
+                        .map(|wait| format!(
+                            "{:?} waits for {:?} on {}",
+                            wait.waiter, wait.holder, wait.lock
+                        ))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    victim.waiter,
+                    victim.lock
+                ),
+                name: victim.lock,
+                holder: victim.waiter,
+            });
+        }
+        Ok(deadlocks)
+    }
+
+    /// Publishes a `Deadlock` event, which aborts the Acquire of its victim.
+    pub fn abort(&self, deadlock: &Deadlock) {
+        warn!("Deadlock: {}", deadlock.description);
+        self.send(Event::Deadlock(deadlock.clone()));
+    }
+
+    /// Breaks each deadlock by aborting its victim and taking it out of the
+    /// queue, see `deadlocks`.
+    pub fn detect(&self) -> Result<Vec<Deadlock>, Error> {
+        let deadlocks = self.deadlocks()?;
+        for deadlock in deadlocks.iter() {
+            self.abort(deadlock);
+            self.dequeued(deadlock.name.clone(), deadlock.holder.clone())?;
+        }
+        Ok(deadlocks)
+    }
+
+    /// Creates a barrier tripping once `parties` participants entered.
+    pub fn barrier_created(&self, name: String, parties: u32) -> Result<(), Error> {
+        debug!("Creating barrier {} for {} parties", name, parties);
+        let mut barriers = self
+            .barriers
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        if barriers.contains_key(&name) {
+            return Err(Error::AlreadyExists(name));
+        }
+        barriers.insert(name.clone(), Barrier::new(parties)?);
+        self.send(Event::Created(name));
+        Ok(())
+    }
+
+    /// Lets `participant` arrive at the barrier, see `Barrier::enter`.
+    pub fn entered(
+        &self,
+        name: String,
+        participant: String,
+        generation: Option<u64>,
+    ) -> Result<(u64, bool), Error> {
+        debug!("{:?} enters barrier {}", participant, name);
+        let (generation, tripped) = self
+            .barriers
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .get_mut(&name)
+            .ok_or_else(|| Error::NotFound(name.clone()))?
+            .enter(participant, generation);
+        self.send(Event::Entered(name.clone()));
+        if tripped {
+            debug!("Barrier {} tripped", name);
+            self.send(Event::Tripped(name));
+        }
+        Ok((generation, tripped))
+    }
+
+    /// Takes `participant` back out of the barrier if it has not tripped yet.
+    pub fn left(&self, name: String, participant: String, generation: u64) -> Result<bool, Error> {
+        Ok(self
+            .barriers
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .get_mut(&name)
+            .ok_or(Error::NotFound(name))?
+            .leave(&participant, generation))
+    }
+
+    pub fn barrier(&self, name: String) -> Result<Barrier, Error> {
+        self.barriers
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .get(&name)
+            .cloned()
+            .ok_or(Error::NotFound(name))
+    }
+
+    /// Creates a latch that opens after counting down from `count`.
+    pub fn latch_created(&self, name: String, count: u32) -> Result<(), Error> {
+        debug!("Creating latch {} at {}", name, count);
+        let mut latches = self
+            .latches
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        if latches.contains_key(&name) {
+            return Err(Error::AlreadyExists(name));
+        }
+        latches.insert(name.clone(), Latch::new(count));
+        self.send(Event::Created(name));
+        Ok(())
+    }
+
+    /// Counts the latch down by `amount` and returns what is left.
+    pub fn counted_down(&self, name: String, amount: u32) -> Result<u32, Error> {
+        let mut latches = self
+            .latches
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        let latch = latches
+            .get_mut(&name)
+            .ok_or_else(|| Error::NotFound(name.clone()))?;
+        let opened = latch.count_down(amount);
+        let count = latch.count();
+        debug!("Latch {} counted down to {}", name, count);
+        self.send(Event::CountedDown(name.clone()));
+        if opened {
+            self.send(Event::Opened(name));
+        }
+        Ok(count)
+    }
+
+    pub fn latch(&self, name: String) -> Result<Latch, Error> {
+        self.latches
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .get(&name)
+            .cloned()
+            .ok_or(Error::NotFound(name))
+    }
+
+    pub fn get(&self, name: String) -> Result<Lock, Error> {
+        self.storage.get(name)
+    }
+
+    pub fn state(&self, name: String) -> Result<bool, Error> {
         debug!("Get state of {}", name);
         Ok(self.storage.get(name)?.locked())
     }
 
+    /// Hands `event` to every watcher that wants it. Watchers whose buffer is
+    /// full are dropped rather than waited for, which ends their stream; they
+    /// can resume from the last revision they got.
     fn send(&self, event: Event) {
-        let handle = match Handle::try_current() {
-            Ok(handle) => handle,
-            Err(_) => tokio::runtime::Runtime::new().unwrap().handle().to_owned(),
-        };
-        let handler = self.clone();
-        handle.spawn_blocking(move || {
-            for sender in match handler.sender.lock() {
+        let (stamped, watchers) = {
+            let mut senders = match self.sender.lock() {
                 Ok(sender) => sender,
                 Err(err) => {
                     debug!("Could not send: {}", err.to_string());
                     return;
                 }
-            }
-            .iter()
-            {
-                match sender.blocking_send(event.to_owned()) {
-                    Ok(_) => {}
-                    Err(err) => debug!("Could not send: {}", err.to_string()),
-                };
-            }
-        });
+            };
+            // Stamped while holding the watchers, so a watch resuming from an
+            // earlier revision gets each event either replayed or sent.
+            let stamped = match self.history.lock() {
+                Ok(mut history) => history.record(event),
+                Err(err) => {
+                    debug!("Could not record: {}", err.to_string());
+                    return;
+                }
+            };
+            senders.retain(|watcher| !watcher.sender.is_closed());
+            let watchers: Vec<Sender<Stamped>> = senders
+                .iter()
+                .filter(|watcher| watcher.filter.matches(&stamped.event))
+                .map(|watcher| watcher.sender.clone())
+                .collect();
+            (stamped, watchers)
+        };
+        let mut lagging = vec![];
+        for sender in watchers {
+            match sender.try_send(stamped.to_owned()) {
+                Ok(_) => {}
+                Err(TrySendError::Full(_)) => lagging.push(sender),
+                Err(err) => debug!("Could not send: {}", err.to_string()),
+            };
+        }
+        if lagging.is_empty() {
+            return;
+        }
+        warn!(
+            "Dropping {} watchers that fell behind at revision {}",
+            lagging.len(),
+            stamped.revision
+        );
+        if let Ok(mut senders) = self.sender.lock() {
+            senders.retain(|watcher| {
+                !lagging
+                    .iter()
+                    .any(|sender| sender.same_channel(&watcher.sender))
+            });
+        }
+    }
+
+    /// Revision of the latest event.
+    pub fn revision(&self) -> Result<u64, Error> {
+        Ok(self
+            .history
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .revision())
+    }
+
+    pub fn watch(&self, size: usize) -> Result<ReceiverStream<Stamped>, Error> {
+        self.watch_filtered(size, Filter::default(), 0)
     }
 
-    pub fn watch(&self, size: usize) -> Result<ReceiverStream<Event>, anyhow::Error> {
-        let (tx, rx) = mpsc::channel(size);
-        self.sender
+    /// Like `watch`, but only events passing `filter` are queued up. With a
+    /// `start` revision other than 0 the events since then are replayed
+    /// first, which fails if they are no longer kept.
+    pub fn watch_filtered(
+        &self,
+        size: usize,
+        filter: Filter,
+        start: u64,
+    ) -> Result<ReceiverStream<Stamped>, Error> {
+        let mut senders = self
+            .sender
             .lock()
-            .map_err(|err| anyhow::Error::msg(err.to_string()))?
-            .push(tx);
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        let replay: Vec<Stamped> = match start {
+            0 => vec![],
+            start => self
+                .history
+                .lock()
+                .map_err(|err| Error::Internal(err.to_string()))?
+                .since(start)
+                .ok_or_else(|| {
+                    Error::OutOfRange(format!("Revision {} is not in the history", start))
+                })?
+                .into_iter()
+                .filter(|stamped| filter.matches(&stamped.event))
+                .collect(),
+        };
+        let (tx, rx) = mpsc::channel(size + replay.len());
+        for stamped in replay {
+            tx.try_send(stamped)
+                .map_err(|err| Error::Internal(err.to_string()))?;
+        }
+        senders.push(Watcher { filter, sender: tx });
         Ok(tokio_stream::wrappers::ReceiverStream::new(rx))
     }
-}
 
-impl<Store> UpdateHandler for Handler<Store>
-where
-    Store: Storage<String, Lock> + Clone,
-{
-    fn on_update(&self, update: Update) {
-        match ok_or_log!(swarm::SwarmMessage::decode(&update.content()[..])).payload {
+    /// Applies a `LockMessage` another node gossiped.
+    fn lock_message(&self, msg: swarm::LockMessage) -> Result<(), Error> {
+        self.witness(msg.clock);
+        let lock_name = msg.name.clone();
+        match swarm::lock_message::Action::from_i32(msg.action) {
+            Some(action) => match action {
+                swarm::lock_message::Action::Created => self
+                    .created(lock_name.clone(), msg.permits, msg.reentrant)
+                    .and_then(|_| {
+                        self.annotated(lock_name, msg.metadata.unwrap_or_default().into())
+                    }),
+                swarm::lock_message::Action::Removed if msg.subtree => {
+                    self.removed_all(lock_name).map(|_| ())
+                }
+                swarm::lock_message::Action::Removed => self.removed(lock_name),
+                swarm::lock_message::Action::Locked => {
+                    let asked = msg.ack.then(|| msg.message_id.clone());
+                    let claimed = self.claimed(msg);
+                    if let Some(message_id) = asked {
+                        if let Err(err) = self.acknowledge(message_id, &claimed) {
+                            warn!("Could not acknowledge: {}", err);
+                        }
+                    }
+                    claimed.map(|_| ())
+                }
+                swarm::lock_message::Action::Unlocked => self
+                    .unlocked(lock_name, msg.holder, msg.permits, msg.force)
+                    .map(|_| ()),
+                swarm::lock_message::Action::Renewed => {
+                    self.renewed(lock_name, msg.holder, time_from_millis(msg.expires_at))
+                }
+                swarm::lock_message::Action::Queued => self
+                    .queued(
+                        lock_name,
+                        msg.holder,
+                        time_from_millis(msg.queued_at).unwrap_or_else(SystemTime::now),
+                        time_from_millis(msg.wait_until),
+                    )
+                    .map(|_| ()),
+                swarm::lock_message::Action::Dequeued => {
+                    self.dequeued(lock_name, msg.holder).map(|_| ())
+                }
+                swarm::lock_message::Action::Expired => {
+                    self.expired(lock_name, msg.token).map(|_| ())
+                }
+            },
+            None => Err(Error::InvalidArgument(String::from(
+                "lock_message has no action",
+            ))),
+        }
+    }
+
+    /// Applies a `LockMessage` committed to the raft log. Every node applies
+    /// the log in the same order, so unlike gossip there are no conflicts to
+    /// settle: an acquisition of a held lock fails, tokens are handed out
+    /// here and holds on reentrant locks count on every node.
+    fn committed_lock(&self, msg: swarm::LockMessage) -> Result<Applied, Error> {
+        self.witness(msg.clock);
+        let name = msg.name.clone();
+        let action = swarm::lock_message::Action::from_i32(msg.action)
+            .ok_or_else(|| Error::InvalidArgument(String::from("lock_message has no action")))?;
+        match action {
+            swarm::lock_message::Action::Locked => {
+                let mode: Mode = swarm::lock_message::Mode::from_i32(msg.mode)
+                    .unwrap_or(swarm::lock_message::Mode::Exclusive)
+                    .into();
+                let expires_at = time_from_millis(msg.expires_at);
+                let stamp = Stamp {
+                    clock: msg.clock,
+                    node: msg.node,
+                };
+                if mode == Mode::Exclusive && !msg.subtree {
+                    if let Some(token) = self.reentered(name.clone(), msg.holder.clone())? {
+                        return Ok(Applied::Token(token));
+                    }
+                }
+                let token = match msg.subtree {
+                    true => self.locked_subtree(name.clone(), msg.holder, expires_at, stamp)?,
+                    false => self.locked(
+                        name.clone(),
+                        msg.holder,
+                        mode,
+                        msg.permits,
+                        expires_at,
+                        stamp,
+                    )?,
+                };
+                self.attached(name, msg.metadata.unwrap_or_default().into())?;
+                Ok(Applied::Token(token))
+            }
+            _ => self.lock_message(msg).map(|_| Applied::Done),
+        }
+    }
+
+    /// Applies an encoded `SwarmMessage` committed to the raft log and
+    /// returns what it came to.
+    pub fn commit(&self, content: &[u8]) -> Result<Applied, Error> {
+        let payload = swarm::SwarmMessage::decode(content)
+            .map_err(|err| Error::Internal(err.to_string()))?
+            .payload;
+        match payload {
+            Some(swarm::swarm_message::Payload::LockMessage(msg)) => self.committed_lock(msg),
+            Some(swarm::swarm_message::Payload::LockBatch(batch)) => {
+                let first = batch.locks.first().cloned().unwrap_or_default();
+                self.witness(first.clock);
+                let tokens = self.locked_many(
+                    first.holder,
+                    time_from_millis(first.expires_at),
+                    batch.locks.into_iter().map(|lock| lock.name).collect(),
+                    Stamp {
+                        clock: first.clock,
+                        node: first.node,
+                    },
+                )?;
+                Ok(Applied::Tokens(tokens))
+            }
+            Some(swarm::swarm_message::Payload::BarrierMessage(msg)) => {
+                match swarm::barrier_message::Action::from_i32(msg.action) {
+                    Some(swarm::barrier_message::Action::Created) => self
+                        .barrier_created(msg.name, msg.parties)
+                        .map(|_| Applied::Done),
+                    Some(swarm::barrier_message::Action::Entered) => {
+                        let (generation, tripped) =
+                            self.entered(msg.name, msg.participant, None)?;
+                        Ok(Applied::Entered(generation, tripped))
+                    }
+                    Some(swarm::barrier_message::Action::Left) => self
+                        .left(msg.name, msg.participant, msg.generation)
+                        .map(|_| Applied::Done),
+                    None => Err(Error::InvalidArgument(String::from(
+                        "barrier_message has no action",
+                    ))),
+                }
+            }
+            Some(swarm::swarm_message::Payload::LatchMessage(msg)) => {
+                match swarm::latch_message::Action::from_i32(msg.action) {
+                    Some(swarm::latch_message::Action::Created) => self
+                        .latch_created(msg.name, msg.count)
+                        .map(|_| Applied::Done),
+                    Some(swarm::latch_message::Action::CountedDown) => {
+                        Ok(Applied::Count(self.counted_down(msg.name, msg.count)?))
+                    }
+                    None => Err(Error::InvalidArgument(String::from(
+                        "latch_message has no action",
+                    ))),
+                }
+            }
+            // Acks and state sync only ever get gossiped.
+            Some(_) | None => Err(Error::InvalidArgument(String::from(
+                "Entry holds no change",
+            ))),
+        }
+    }
+
+    /// Applies an encoded `SwarmMessage` another node gossiped.
+    /// Repeats of a message seen lately are dropped.
+    pub fn apply(&self, content: &[u8]) {
+        let payload = ok_or_log!(swarm::SwarmMessage::decode(content)).payload;
+        if let Some(message_id) = payload.as_ref().and_then(|msg| msg.message_id()) {
+            if !ok_or_log!(self.remember(message_id)) {
+                debug!("Dropping duplicate message {}", message_id);
+                self.duplicates.fetch_add(1, Ordering::SeqCst);
+                return;
+            }
+        }
+        match payload {
             Some(msg) => match msg {
                 swarm::swarm_message::Payload::LockMessage(msg) => {
-                    let lock_name = msg.name;
-                    match swarm::lock_message::Action::from_i32(msg.action) {
-                        Some(action) => match action {
-                            swarm::lock_message::Action::Created => self.created(lock_name),
-                            swarm::lock_message::Action::Removed => self.removed(lock_name),
-                            swarm::lock_message::Action::Locked => self.locked(lock_name),
-                            swarm::lock_message::Action::Unlocked => self.unlocked(lock_name),
-                        },
-                        None => {
-                            warn!("lock_message has no action");
-                            return;
+                    ok_or_log!(self.lock_message(msg))
+                }
+                swarm::swarm_message::Payload::BarrierMessage(msg) => {
+                    match swarm::barrier_message::Action::from_i32(msg.action) {
+                        Some(swarm::barrier_message::Action::Created) => {
+                            ok_or_log!(self.barrier_created(msg.name, msg.parties))
+                        }
+                        Some(swarm::barrier_message::Action::Entered) => ok_or_log!(self
+                            .entered(msg.name, msg.participant, Some(msg.generation))
+                            .map(|_| ())),
+                        Some(swarm::barrier_message::Action::Left) => ok_or_log!(self
+                            .left(msg.name, msg.participant, msg.generation)
+                            .map(|_| ())),
+                        None => warn!("barrier_message has no action"),
+                    }
+                }
+                swarm::swarm_message::Payload::LatchMessage(msg) => {
+                    match swarm::latch_message::Action::from_i32(msg.action) {
+                        Some(swarm::latch_message::Action::Created) => {
+                            ok_or_log!(self.latch_created(msg.name, msg.count))
+                        }
+                        Some(swarm::latch_message::Action::CountedDown) => {
+                            ok_or_log!(self.counted_down(msg.name, msg.count).map(|_| ()))
                         }
+                        None => warn!("latch_message has no action"),
                     }
                 }
+                swarm::swarm_message::Payload::Ack(ack) => ok_or_log!(self.acked(ack)),
+                swarm::swarm_message::Payload::Digest(digest) => {
+                    ok_or_log!(self.compared(digest))
+                }
+                swarm::swarm_message::Payload::Pull(pull) => ok_or_log!(self.pulled(pull)),
+                swarm::swarm_message::Payload::Snapshot(snapshot) => {
+                    ok_or_log!(self.restored(snapshot))
+                }
+                swarm::swarm_message::Payload::LockBatch(batch) => {
+                    for lock in batch.locks.iter() {
+                        self.witness(lock.clock);
+                    }
+                    ok_or_log!(self.claimed_batch(batch).map(|_| ()))
+                }
             },
             None => return,
         }
     }
 }
+
+impl<Store> UpdateHandler for Handler<Store>
+where
+    Store: Storage<String, Lock> + Clone,
+{
+    fn on_update(&self, update: Update) {
+        self.apply(&update.content()[..])
+    }
+}
+
+/// Acquires `lock` for the holder of the gossiped `msg`. If that conflicts
+/// with the current holders, the earlier acquisitions keep the lock, see
+/// `Lock::contest`. Returns the holders `msg` took the lock from, or the one
+/// that kept it.
+fn settle(lock: &mut Lock, msg: &swarm::LockMessage) -> Result<Result<Vec<String>, String>, Error> {
+    let mode: Mode = swarm::lock_message::Mode::from_i32(msg.mode)
+        .unwrap_or(swarm::lock_message::Mode::Exclusive)
+        .into();
+    let expires_at = time_from_millis(msg.expires_at);
+    let stamp = Stamp {
+        clock: msg.clock,
+        node: msg.node.clone(),
+    };
+    // Takes the token the winner's peer handed out unless this node is
+    // ahead already, tokens never go back.
+    let acquire = |lock: &mut Lock| match msg.subtree {
+        true => lock.lock_subtree(msg.holder.clone(), expires_at, msg.token),
+        false => lock.lock(msg.holder.clone(), mode, msg.permits, expires_at, msg.token),
+    };
+    let losers = match acquire(lock) {
+        Ok(()) => vec![],
+        Err(Error::AlreadyLocked) => {
+            let losers = match lock.contest(&msg.holder, mode, msg.permits, &stamp) {
+                Ok(losers) if !losers.is_empty() => losers,
+                Ok(_) => return Err(Error::AlreadyLocked),
+                Err(winner) => return Ok(Err(winner)),
+            };
+            for loser in losers.iter() {
+                lock.evict(loser);
+            }
+            acquire(lock)?;
+            losers
+        }
+        Err(err) => return Err(err),
+    };
+    lock.stamped(&msg.holder, stamp);
+    lock.attach(msg.metadata.clone().unwrap_or_default().into());
+    Ok(Ok(losers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::memory::{Config, Memory};
+
+    fn handler(node: &str) -> Handler<Memory<String, Lock>> {
+        peer(node).0
+    }
+
+    /// A handler along with what it gossips.
+    fn peer(
+        node: &str,
+    ) -> (
+        Handler<Memory<String, Lock>>,
+        mpsc::UnboundedReceiver<Vec<u8>>,
+    ) {
+        let storage = Memory::new(Config {
+            bitmap_size: 1024,
+            items_count: 64,
+        });
+        let (outbox, gossip) = mpsc::unbounded_channel();
+        (Handler::new(storage, node.to_string(), outbox), gossip)
+    }
+
+    /// Hands everything gossiped so far to `to`, returns how much that was.
+    fn deliver(
+        gossip: &mut mpsc::UnboundedReceiver<Vec<u8>>,
+        to: &Handler<Memory<String, Lock>>,
+    ) -> usize {
+        let mut delivered = 0;
+        while let Ok(msg) = gossip.try_recv() {
+            to.apply(&msg);
+            delivered += 1;
+        }
+        delivered
+    }
+
+    fn gossiped(handler: &Handler<Memory<String, Lock>>, payload: swarm::swarm_message::Payload) {
+        let msg = swarm::SwarmMessage {
+            payload: Some(payload),
+        };
+        handler.apply(&msg.encode_to_vec());
+    }
+
+    fn claim(name: &str, holder: &str, clock: u64, node: &str) -> swarm::LockMessage {
+        swarm::LockMessage {
+            name: name.to_string(),
+            action: swarm::lock_message::Action::Locked.into(),
+            message_id: nano_id::base64::<21>(),
+            holder: holder.to_string(),
+            clock,
+            node: node.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Conflicts reported to watchers so far.
+    fn lost(events: &mut mpsc::Receiver<Stamped>) -> Vec<Conflict> {
+        let mut lost = vec![];
+        while let Ok(stamped) = events.try_recv() {
+            if let Event::Lost(conflict) = stamped.event {
+                lost.push(conflict);
+            }
+        }
+        lost
+    }
+
+    #[test]
+    fn keeps_the_whole_store_from_bulk_removal() {
+        let handler = handler("node");
+        handler.created("a".to_string(), 0, false).unwrap();
+        assert!(matches!(
+            handler.removed_all(String::new()),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(handler.get("a".to_string()).is_ok());
+    }
+
+    #[test]
+    fn holds_a_subtree_or_a_lock_below_it_never_both() {
+        for _ in 0..100 {
+            let handler = handler("node");
+            handler.created("a".to_string(), 0, false).unwrap();
+            handler.created("a/b".to_string(), 0, false).unwrap();
+            let subtree = {
+                let handler = handler.clone();
+                std::thread::spawn(move || {
+                    let stamp = handler.tick();
+                    handler.locked_subtree("a".to_string(), "x".to_string(), None, stamp)
+                })
+            };
+            let stamp = handler.tick();
+            let below = handler.locked(
+                "a/b".to_string(),
+                "y".to_string(),
+                Mode::Exclusive,
+                0,
+                None,
+                stamp,
+            );
+            let subtree = subtree.join().unwrap();
+            assert!(subtree.is_err() || below.is_err());
+        }
+    }
+
+    #[test]
+    fn reports_the_later_of_two_acquisitions_as_lost() {
+        let (n1, n2) = (handler("n1"), handler("n2"));
+        let mut lost_on = vec![];
+        for (handler, holder, clock) in [(&n1, "x", 1), (&n2, "y", 2)] {
+            handler.created("a".to_string(), 0, false).unwrap();
+            let stamp = Stamp {
+                clock,
+                node: handler.node.clone(),
+            };
+            handler
+                .locked(
+                    "a".to_string(),
+                    holder.to_string(),
+                    Mode::Exclusive,
+                    0,
+                    None,
+                    stamp,
+                )
+                .unwrap();
+            lost_on.push(handler.watch(100).unwrap().into_inner());
+        }
+        let claims = [
+            (&n1, claim("a", "y", 2, "n2")),
+            (&n2, claim("a", "x", 1, "n1")),
+        ];
+        for (handler, claim) in claims {
+            gossiped(handler, swarm::swarm_message::Payload::LockMessage(claim));
+        }
+        for (handler, events) in [&n1, &n2].iter().zip(lost_on.iter_mut()) {
+            assert_eq!(
+                handler.get("a".to_string()).unwrap().holder().as_deref(),
+                Some("x")
+            );
+            let lost = lost(events);
+            assert_eq!(lost.len(), 1);
+            assert_eq!(
+                (lost[0].holder.as_str(), lost[0].winner.as_str()),
+                ("y", "x")
+            );
+        }
+    }
+
+    #[test]
+    fn repairs_a_lock_its_digest_disagrees_on() {
+        let (n1, mut from_n1) = peer("n1");
+        let (n2, mut from_n2) = peer("n2");
+        for handler in [&n1, &n2] {
+            handler.created("a".to_string(), 0, false).unwrap();
+        }
+        let stamp = n1.tick();
+        n1.locked(
+            "a".to_string(),
+            "x".to_string(),
+            Mode::Exclusive,
+            0,
+            None,
+            stamp,
+        )
+        .unwrap();
+        n1.digest().unwrap();
+        while deliver(&mut from_n1, &n2) + deliver(&mut from_n2, &n1) > 0 {}
+        let repaired = n2.get("a".to_string()).unwrap();
+        assert_eq!(repaired.holder().as_deref(), Some("x"));
+        assert_eq!(
+            repaired.fingerprint(),
+            n1.get("a".to_string()).unwrap().fingerprint()
+        );
+    }
+
+    #[test]
+    fn loses_a_whole_batch_over_one_lock() {
+        let handler = handler("n1");
+        for name in ["a", "b"] {
+            handler.created(name.to_string(), 0, false).unwrap();
+        }
+        let stamp = Stamp {
+            clock: 1,
+            node: String::from("n1"),
+        };
+        handler
+            .locked(
+                "b".to_string(),
+                "x".to_string(),
+                Mode::Exclusive,
+                0,
+                None,
+                stamp,
+            )
+            .unwrap();
+        let mut events = handler.watch(100).unwrap().into_inner();
+        gossiped(
+            &handler,
+            swarm::swarm_message::Payload::LockBatch(swarm::LockBatch {
+                message_id: nano_id::base64::<21>(),
+                locks: vec![claim("a", "y", 5, "n2"), claim("b", "y", 5, "n2")],
+            }),
+        );
+        assert!(!handler.get("a".to_string()).unwrap().locked());
+        assert_eq!(
+            handler.get("b".to_string()).unwrap().holder().as_deref(),
+            Some("x")
+        );
+        let lost = lost(&mut events);
+        assert_eq!(lost.len(), 1);
+        assert_eq!(
+            (lost[0].holder.as_str(), lost[0].winner.as_str()),
+            ("y", "x")
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use super::event::{Event, Stamped};
+use std::collections::VecDeque;
+
+/// The latest events along with the revisions they were stamped with.
+pub struct History {
+    revision: u64,
+    events: VecDeque<Stamped>,
+    capacity: usize,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        History {
+            revision: 0,
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Revision of the latest event, 0 before the first one.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Stamps `event` with the next revision and records it, dropping the
+    /// oldest event once the history is full.
+    pub fn record(&mut self, event: Event) -> Stamped {
+        self.revision += 1;
+        let stamped = Stamped {
+            revision: self.revision,
+            event,
+        };
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(stamped.clone());
+        stamped
+    }
+
+    /// Recorded events from revision `start` on, `None` if some of them
+    /// were already dropped or `start` is past the next revision.
+    pub fn since(&self, start: u64) -> Option<Vec<Stamped>> {
+        let compacted = match self.events.front() {
+            Some(oldest) => oldest.revision - 1,
+            None => self.revision,
+        };
+        if start <= compacted && start <= self.revision || start > self.revision + 1 {
+            return None;
+        }
+        Some(
+            self.events
+                .iter()
+                .filter(|stamped| stamped.revision >= start)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revisions(events: Option<Vec<Stamped>>) -> Option<Vec<u64>> {
+        events.map(|events| events.iter().map(|stamped| stamped.revision).collect())
+    }
+
+    #[test]
+    fn stamps_events_with_increasing_revisions() {
+        let mut history = History::new(4);
+        assert_eq!(history.revision(), 0);
+        assert_eq!(history.since(1).unwrap().len(), 0);
+        assert_eq!(history.record(Event::Created("a".to_string())).revision, 1);
+        assert_eq!(history.record(Event::Locked("a".to_string())).revision, 2);
+        assert_eq!(history.revision(), 2);
+        assert_eq!(revisions(history.since(2)), Some(vec![2]));
+    }
+
+    #[test]
+    fn drops_the_oldest_once_full() {
+        let mut history = History::new(2);
+        for _ in 0..3 {
+            history.record(Event::Locked("a".to_string()));
+        }
+        assert_eq!(revisions(history.since(1)), None);
+        assert_eq!(revisions(history.since(2)), Some(vec![2, 3]));
+        assert_eq!(revisions(history.since(4)), Some(vec![]));
+    }
+
+    #[test]
+    fn refuses_revisions_past_the_next() {
+        let mut history = History::new(2);
+        assert_eq!(revisions(history.since(2)), None);
+        history.record(Event::Locked("a".to_string()));
+        assert_eq!(revisions(history.since(2)), Some(vec![]));
+        assert_eq!(revisions(history.since(3)), None);
+    }
+}
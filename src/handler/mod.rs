@@ -1,3 +1,6 @@
 pub mod handler;
 pub use handler::Handler;
 pub mod event;
+pub mod graph;
+pub mod history;
+pub mod seen;
@@ -0,0 +1,73 @@
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Ids of the latest swarm messages, forgotten once they are older than the
+/// window or the set is full.
+pub struct Seen {
+    ids: HashSet<String>,
+    order: VecDeque<(Instant, String)>,
+    capacity: usize,
+    window: Duration,
+}
+
+impl Seen {
+    pub fn new(capacity: usize, window: Duration) -> Self {
+        Seen {
+            ids: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            capacity,
+            window,
+        }
+    }
+
+    /// Records `id` and tells whether it is new, false for a repeat within
+    /// the window.
+    pub fn insert(&mut self, id: &str, now: Instant) -> bool {
+        while let Some((at, _)) = self.order.front() {
+            if now.duration_since(*at) < self.window && self.order.len() < self.capacity {
+                break;
+            }
+            if let Some((_, old)) = self.order.pop_front() {
+                self.ids.remove(&old);
+            }
+        }
+        if !self.ids.insert(id.to_string()) {
+            return false;
+        }
+        self.order.push_back((now, id.to_string()));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_within_the_window_are_not_new() {
+        let now = Instant::now();
+        let mut seen = Seen::new(8, Duration::from_secs(10));
+        assert!(seen.insert("a", now));
+        assert!(!seen.insert("a", now + Duration::from_secs(9)));
+        assert!(seen.insert("b", now + Duration::from_secs(9)));
+    }
+
+    #[test]
+    fn forgets_ids_older_than_the_window() {
+        let now = Instant::now();
+        let mut seen = Seen::new(8, Duration::from_secs(10));
+        assert!(seen.insert("a", now));
+        assert!(seen.insert("a", now + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn forgets_the_oldest_once_full() {
+        let now = Instant::now();
+        let mut seen = Seen::new(2, Duration::from_secs(10));
+        assert!(seen.insert("a", now));
+        assert!(seen.insert("b", now));
+        assert!(seen.insert("c", now));
+        assert!(!seen.insert("c", now));
+        assert!(seen.insert("a", now));
+    }
+}
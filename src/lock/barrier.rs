@@ -0,0 +1,89 @@
+use crate::error::Error;
+use crate::util::Fingerprint;
+use std::collections::BTreeSet;
+
+/// Cyclic barrier releasing its participants once `parties` of them arrived.
+#[derive(Clone, Debug)]
+pub struct Barrier {
+    parties: u32,
+    arrived: BTreeSet<String>,
+    generation: u64,
+}
+
+impl Barrier {
+    pub fn new(parties: u32) -> Result<Self, Error> {
+        if parties == 0 {
+            return Err(Error::InvalidArgument(String::from(
+                "A barrier needs at least one party",
+            )));
+        }
+        Ok(Barrier {
+            parties,
+            arrived: BTreeSet::new(),
+            generation: 0,
+        })
+    }
+
+    pub fn parties(&self) -> u32 {
+        self.parties
+    }
+
+    pub fn arrived(&self) -> u32 {
+        self.arrived.len() as u32
+    }
+
+    /// Counts the trips so far, participants wait for it to move on.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Lets `participant` arrive at `generation`, the current one if `None`.
+    /// Returns the generation it arrived at and whether it tripped the
+    /// barrier. Arrivals at a generation that already tripped are ignored.
+    pub fn enter(&mut self, participant: String, generation: Option<u64>) -> (u64, bool) {
+        let generation = generation.unwrap_or(self.generation);
+        if generation != self.generation {
+            return (generation, false);
+        }
+        self.arrived.insert(participant);
+        if self.arrived() < self.parties {
+            return (generation, false);
+        }
+        self.arrived.clear();
+        self.generation += 1;
+        (generation, true)
+    }
+
+    /// Takes `participant` back out of `generation` before it tripped,
+    /// returns whether it was waiting.
+    pub fn leave(&mut self, participant: &str, generation: u64) -> bool {
+        generation == self.generation && self.arrived.remove(participant)
+    }
+
+    /// Participants waiting for the current generation to trip.
+    pub fn participants(&self) -> Vec<String> {
+        self.arrived.iter().cloned().collect()
+    }
+
+    /// Moves on to `generation` of another peer if it is ahead of ours,
+    /// dropping who arrived at ours. Returns whether it moved.
+    pub fn fence(&mut self, generation: u64) -> bool {
+        if generation <= self.generation {
+            return false;
+        }
+        self.arrived.clear();
+        self.generation = generation;
+        true
+    }
+
+    /// Hash of the participants waiting, equal on peers that agree on the
+    /// barrier at the same generation.
+    pub fn fingerprint(&self) -> u64 {
+        let mut fingerprint = Fingerprint::default();
+        fingerprint.u64(self.arrived.len() as u64);
+        for participant in self.arrived.iter() {
+            fingerprint.str(participant);
+        }
+        fingerprint.finish()
+    }
+}
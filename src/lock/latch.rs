@@ -0,0 +1,28 @@
+/// Countdown latch opening once its count reaches zero.
+#[derive(Clone, Debug)]
+pub struct Latch {
+    count: u32,
+}
+
+impl Latch {
+    pub fn new(count: u32) -> Self {
+        Latch { count }
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn open(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Counts down by `amount`, returns whether that opened the latch.
+    pub fn count_down(&mut self, amount: u32) -> bool {
+        if self.open() {
+            return false;
+        }
+        self.count = self.count.saturating_sub(amount);
+        self.open()
+    }
+}
@@ -1,48 +1,905 @@
-use anyhow::Error;
+use crate::error::Error;
+use crate::util::Fingerprint;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::SystemTime;
 
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(PartialEq, Clone, Debug)]
 pub enum State {
     Locked,
     Unlocked,
+    /// Held by a set of readers.
+    Shared(BTreeSet<String>),
+    /// Semaphore permits taken per holder.
+    Counted(BTreeMap<String, u32>),
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Mode {
+    Exclusive,
+    Shared,
+}
+
+/// Largest payload clients may attach, it is gossiped with every change.
+pub const MAX_PAYLOAD: usize = 4 * 1024;
+
+/// Data clients attach to a lock, the server does not interpret it.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Metadata {
+    pub labels: BTreeMap<String, String>,
+    pub description: String,
+    pub payload: Vec<u8>,
+}
+
+impl Metadata {
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty() && self.description.is_empty() && self.payload.is_empty()
+    }
+
+    /// Fails for a payload over `MAX_PAYLOAD` bytes.
+    pub fn check(&self) -> Result<(), Error> {
+        match self.payload.len() {
+            len if len > MAX_PAYLOAD => Err(Error::InvalidArgument(format!(
+                "Payload of {} bytes exceeds the limit of {} bytes",
+                len, MAX_PAYLOAD
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds the labels of `other` and replaces description and payload with
+    /// the ones given.
+    fn merge(&mut self, other: Metadata) {
+        self.labels.extend(other.labels);
+        if !other.description.is_empty() {
+            self.description = other.description;
+        }
+        if !other.payload.is_empty() {
+            self.payload = other.payload;
+        }
+    }
+}
+
+/// Lamport time of an acquisition and the node it happened on, which breaks
+/// ties. Of two conflicting acquisitions the earlier one wins.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct Stamp {
+    pub clock: u64,
+    pub node: String,
 }
 
 #[derive(Clone, Debug)]
 pub struct Lock {
     state: Arc<State>,
+    holder: Option<String>,
+    expires_at: Option<SystemTime>,
+    token: u64,
+    permits: u32,
+    /// Waiting holders, since when they wait and until when at most.
+    waiters: VecDeque<(String, SystemTime, Option<SystemTime>)>,
+    reentrant: bool,
+    holds: u32,
+    /// Whether the exclusive holder also holds every lock below this one.
+    subtree: bool,
+    /// When each holder acquired the lock.
+    stamps: BTreeMap<String, Stamp>,
+    /// Attached on create, stays with the lock.
+    metadata: Metadata,
+    /// Attached by the current holders, dropped once the lock is released.
+    attached: Metadata,
 }
 
-pub type LockError = Error;
-
 impl Lock {
     pub fn new() -> Self {
         Lock {
             state: Arc::new(State::Unlocked),
+            holder: None,
+            expires_at: None,
+            token: 0,
+            permits: 0,
+            waiters: VecDeque::new(),
+            reentrant: false,
+            holds: 0,
+            subtree: false,
+            stamps: BTreeMap::new(),
+            metadata: Metadata::default(),
+            attached: Metadata::default(),
+        }
+    }
+
+    /// A lock its exclusive holder may take again, and has to unlock as often.
+    pub fn reentrant() -> Self {
+        Lock {
+            reentrant: true,
+            ..Self::new()
+        }
+    }
+
+    /// A counting semaphore handing out up to `permits` permits.
+    pub fn semaphore(permits: u32) -> Self {
+        Lock {
+            permits,
+            ..Self::new()
         }
     }
 
+    /// Whether the lock is held, exclusively, shared or by any semaphore permit.
     pub fn locked(&self) -> bool {
-        self.state.eq(&Arc::new(State::Locked))
+        self.state.ne(&Arc::new(State::Unlocked))
+    }
+
+    pub fn shared(&self) -> bool {
+        matches!(*self.state, State::Shared(_))
+    }
+
+    /// Exclusive holder of the lock.
+    pub fn holder(&self) -> Option<String> {
+        self.holder.clone()
+    }
+
+    /// Everyone holding the lock, in either mode.
+    pub fn holders(&self) -> Vec<String> {
+        match &*self.state {
+            State::Locked => self.holder.clone().into_iter().collect(),
+            State::Unlocked => vec![],
+            State::Shared(readers) => readers.iter().cloned().collect(),
+            State::Counted(permits) => permits.keys().cloned().collect(),
+        }
+    }
+
+    pub fn is_reentrant(&self) -> bool {
+        self.reentrant
+    }
+
+    /// How often the exclusive holder took the lock without unlocking it.
+    pub fn holds(&self) -> u32 {
+        self.holds
+    }
+
+    /// Whether the lock is held together with the namespace below it.
+    pub fn subtree(&self) -> bool {
+        self.subtree && self.locked()
+    }
+
+    pub fn stamp(&self) -> Option<&Stamp> {
+        self.stamps.values().max()
+    }
+
+    /// When `holder` acquired the lock.
+    pub fn stamp_of(&self, holder: &str) -> Option<&Stamp> {
+        self.stamps.get(holder)
+    }
+
+    /// Records when the lock got acquired.
+    pub fn stamped(&mut self, holder: &str, stamp: Stamp) {
+        self.stamps.insert(holder.to_string(), stamp);
+    }
+
+    /// What was attached on create, overlaid with what the current holders
+    /// attached.
+    pub fn metadata(&self) -> Metadata {
+        let mut metadata = self.metadata.clone();
+        metadata.merge(self.attached.clone());
+        metadata
+    }
+
+    /// What was attached on create.
+    pub fn annotations(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// What the current holders attached.
+    pub fn attachments(&self) -> &Metadata {
+        &self.attached
+    }
+
+    /// Adds the labels of `metadata` to the lock and replaces description
+    /// and payload with the ones given.
+    pub fn annotate(&mut self, metadata: Metadata) {
+        self.metadata.merge(metadata);
+    }
+
+    /// Like `annotate`, but only while the lock is held.
+    pub fn attach(&mut self, metadata: Metadata) {
+        self.attached.merge(metadata);
+    }
+
+    /// Total permits of a semaphore, 0 for plain locks.
+    pub fn permits(&self) -> u32 {
+        self.permits
+    }
+
+    /// Permits currently taken, for plain locks the number of holders.
+    pub fn used(&self) -> u32 {
+        match &*self.state {
+            State::Counted(permits) => permits.values().sum(),
+            _ => self.holders().len() as u32,
+        }
+    }
+
+    /// Permits `holder` took from a semaphore.
+    pub fn taken(&self, holder: &str) -> u32 {
+        match &*self.state {
+            State::Counted(permits) => permits.get(holder).copied().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// Fencing token of the latest acquisition, strictly increasing per lock.
+    pub fn token(&self) -> u64 {
+        self.token
     }
 
-    pub fn lock(&mut self) -> Option<LockError> {
-        match self.locked() {
-            true => Some(Error::msg("Already locked")),
-            false => {
+    /// Adopts the fencing token of another peer if it is ahead of ours.
+    pub fn fence(&mut self, token: u64) {
+        self.token = self.token.max(token);
+    }
+
+    /// Hash of the fencing token, the holders and the waiters, equal on
+    /// peers that agree on the lock.
+    pub fn fingerprint(&self) -> u64 {
+        let mut fingerprint = Fingerprint::default();
+        fingerprint.u64(self.token).u64(self.shared() as u64);
+        let holders = self.holders();
+        fingerprint.u64(holders.len() as u64);
+        for holder in holders.iter() {
+            fingerprint.str(holder).u64(u64::from(self.taken(holder)));
+        }
+        let waiters = self.waiters();
+        fingerprint.u64(waiters.len() as u64);
+        for waiter in waiters.iter() {
+            fingerprint.str(waiter);
+        }
+        fingerprint.finish()
+    }
+
+    /// Whether the lock is held past its lease deadline.
+    pub fn expired(&self, now: SystemTime) -> bool {
+        match self.expires_at {
+            Some(deadline) => self.locked() && deadline <= now,
+            None => false,
+        }
+    }
+
+    /// Acquires the lock for `holder` and advances the fencing token.
+    /// A `token` handed out by another peer is adopted if it is ahead of ours.
+    ///
+    /// Shared acquisitions join the current readers. Readers share one lease
+    /// that lasts until the latest deadline any of them asked for.
+    ///
+    /// Semaphores ignore `mode` and hand out `permits` permits, at least one.
+    ///
+    /// A waiting holder leaves the wait queue once it takes the lock, see
+    /// `in_turn` for who may.
+    pub fn lock(
+        &mut self,
+        holder: String,
+        mode: Mode,
+        permits: u32,
+        expires_at: Option<SystemTime>,
+        token: u64,
+    ) -> Result<(), Error> {
+        match self.permits {
+            0 => self.hold(holder.clone(), mode, permits, expires_at)?,
+            _ => self.take(holder.clone(), permits.max(1), expires_at)?,
+        }
+        self.dequeue(&holder);
+        self.token = token.max(self.token + 1);
+        Ok(())
+    }
+
+    /// Acquires the lock exclusively along with the namespace below it,
+    /// checking for held locks in there is up to the caller.
+    pub fn lock_subtree(
+        &mut self,
+        holder: String,
+        expires_at: Option<SystemTime>,
+        token: u64,
+    ) -> Result<(), Error> {
+        if self.permits > 0 {
+            return Err(Error::InvalidArgument(String::from(
+                "Semaphores cannot hold a subtree",
+            )));
+        }
+        self.lock(holder, Mode::Exclusive, 0, expires_at, token)?;
+        self.subtree = true;
+        Ok(())
+    }
+
+    fn hold(
+        &mut self,
+        holder: String,
+        mode: Mode,
+        permits: u32,
+        expires_at: Option<SystemTime>,
+    ) -> Result<(), Error> {
+        if permits > 1 {
+            return Err(Error::InvalidArgument(String::from(
+                "Only semaphores hand out more than one permit",
+            )));
+        }
+        match (mode, &*self.state) {
+            (Mode::Exclusive, State::Unlocked) => {
                 *Arc::make_mut(&mut self.state) = State::Locked;
-                None
+                self.holder = Some(holder);
+                self.holds = 1;
+                self.expires_at = expires_at;
+            }
+            (Mode::Shared, State::Unlocked) => {
+                *Arc::make_mut(&mut self.state) = State::Shared(BTreeSet::from([holder]));
+                self.expires_at = expires_at;
+            }
+            (Mode::Shared, State::Shared(readers)) if !readers.contains(&holder) => {
+                if let State::Shared(readers) = Arc::make_mut(&mut self.state) {
+                    readers.insert(holder);
+                }
+                self.expires_at = latest(self.expires_at, expires_at);
+            }
+            _ => return Err(Error::AlreadyLocked),
+        }
+        Ok(())
+    }
+
+    fn take(
+        &mut self,
+        holder: String,
+        permits: u32,
+        expires_at: Option<SystemTime>,
+    ) -> Result<(), Error> {
+        if permits > self.permits {
+            return Err(Error::InvalidArgument(format!(
+                "Semaphore has only {} permits",
+                self.permits
+            )));
+        }
+        if self.used() + permits > self.permits {
+            return Err(Error::AlreadyLocked);
+        }
+        match Arc::make_mut(&mut self.state) {
+            State::Counted(taken) => {
+                *taken.entry(holder).or_default() += permits;
+                self.expires_at = latest(self.expires_at, expires_at);
+            }
+            state => {
+                *state = State::Counted(BTreeMap::from([(holder, permits)]));
+                self.expires_at = expires_at;
+            }
+        }
+        Ok(())
+    }
+
+    /// Settles the conflict between the current holders and an acquisition
+    /// by `holder` at `stamp` that `lock` refused: whoever acquired first
+    /// keeps the lock. Returns the holders that came later and have to give
+    /// way, as many as it takes to fit the acquisition in, or else the
+    /// earlier holder the acquisition has to give way to.
+    pub fn contest(
+        &self,
+        holder: &str,
+        mode: Mode,
+        permits: u32,
+        stamp: &Stamp,
+    ) -> Result<Vec<String>, String> {
+        // Holders that do not know when they acquired the lock count as first.
+        let earlier = |other: &String| match self.stamps.get(other) {
+            Some(own) => own < stamp,
+            None => true,
+        };
+        let first = |others: Vec<String>| {
+            others
+                .into_iter()
+                .min_by_key(|other| self.stamps.get(other))
+                .unwrap_or_default()
+        };
+        let others: Vec<String> = self
+            .holders()
+            .into_iter()
+            .filter(|other| other != holder)
+            .collect();
+        if self.permits > 0 {
+            let wanted = permits.max(1);
+            let mut free = self.permits.saturating_sub(self.used());
+            let mut later: Vec<String> = others.iter().filter(|o| !earlier(o)).cloned().collect();
+            later.sort_by(|one, other| self.stamps.get(other).cmp(&self.stamps.get(one)));
+            let mut losers = vec![];
+            for other in later {
+                if free >= wanted {
+                    break;
+                }
+                free += self.taken(&other);
+                losers.push(other);
+            }
+            return match free >= wanted {
+                true => Ok(losers),
+                false => Err(first(others.into_iter().filter(earlier).collect())),
+            };
+        }
+        let conflicting = match (mode, &*self.state) {
+            (Mode::Shared, State::Shared(_)) => vec![],
+            _ => others,
+        };
+        match conflicting.iter().any(earlier) {
+            true => Err(first(conflicting.into_iter().filter(earlier).collect())),
+            false => Ok(conflicting),
+        }
+    }
+
+    /// Takes away whatever `holder` holds of the lock, after it lost a
+    /// conflict.
+    pub fn evict(&mut self, holder: &str) {
+        let last = match Arc::make_mut(&mut self.state) {
+            State::Locked => self.holder.as_deref() == Some(holder),
+            State::Shared(readers) => readers.remove(holder) && readers.is_empty(),
+            State::Counted(taken) => taken.remove(holder).is_some() && taken.is_empty(),
+            State::Unlocked => false,
+        };
+        self.stamps.remove(holder);
+        if last {
+            self.release();
+        }
+    }
+
+    /// Fails while others are waiting for the lock, unless `holder` is the
+    /// first of them. Acquisitions another peer handed out were checked there.
+    pub fn in_turn(&self, holder: &str) -> Result<(), Error> {
+        match self.waiters.front() {
+            Some((next, _, _)) if next != holder => Err(Error::AlreadyLocked),
+            _ => Ok(()),
+        }
+    }
+
+    /// Takes another hold if the lock is reentrant and `holder` owns it
+    /// exclusively. The fencing token stays the same.
+    pub fn reenter(&mut self, holder: &str) -> bool {
+        if !self.reentrant || *self.state != State::Locked || self.holder.as_deref() != Some(holder)
+        {
+            return false;
+        }
+        self.holds += 1;
+        true
+    }
+
+    /// Holders waiting for the lock, in arrival order.
+    pub fn waiters(&self) -> Vec<String> {
+        self.waiters
+            .iter()
+            .map(|(waiter, _, _)| waiter.clone())
+            .collect()
+    }
+
+    /// When `holder` started waiting for the lock.
+    pub fn waiting_since(&self, holder: &str) -> Option<SystemTime> {
+        self.waiters
+            .iter()
+            .find(|(waiter, _, _)| waiter == holder)
+            .map(|(_, since, _)| *since)
+    }
+
+    /// When `holder` gives up waiting for the lock, `None` if it never does.
+    pub fn waiting_until(&self, holder: &str) -> Option<SystemTime> {
+        self.waiters
+            .iter()
+            .find(|(waiter, _, _)| waiter == holder)
+            .and_then(|(_, _, until)| *until)
+    }
+
+    /// Waiters that meant to give up by `now`.
+    pub fn lapsed(&self, now: SystemTime) -> Vec<String> {
+        self.waiters
+            .iter()
+            .filter(|(_, _, until)| matches!(until, Some(until) if *until <= now))
+            .map(|(waiter, _, _)| waiter.clone())
+            .collect()
+    }
+
+    /// Place of `holder` in the wait queue, starting at 1.
+    pub fn position(&self, holder: &str) -> Option<usize> {
+        self.waiters
+            .iter()
+            .position(|(waiter, _, _)| waiter == holder)
+            .map(|index| index + 1)
+    }
+
+    /// Lines `holder`, waiting since `since` until `until` at most, up
+    /// behind those waiting longer and returns its place in the queue. If it
+    /// is already waiting, only `until` moves.
+    /// Waiters since the same time line up by name, so every peer orders the
+    /// queue alike however the messages arrived.
+    pub fn enqueue(
+        &mut self,
+        holder: String,
+        since: SystemTime,
+        until: Option<SystemTime>,
+    ) -> usize {
+        if let Some(index) = self
+            .waiters
+            .iter()
+            .position(|(waiter, _, _)| *waiter == holder)
+        {
+            self.waiters[index].2 = until;
+            return index + 1;
+        }
+        let index = self
+            .waiters
+            .iter()
+            .take_while(|(waiter, waiting, _)| (*waiting, waiter) < (since, &holder))
+            .count();
+        self.waiters.insert(index, (holder, since, until));
+        index + 1
+    }
+
+    /// Takes `holder` out of the wait queue, returns whether it was waiting.
+    pub fn dequeue(&mut self, holder: &str) -> bool {
+        let waiting = self.waiters.len();
+        self.waiters.retain(|(waiter, _, _)| waiter != holder);
+        self.waiters.len() != waiting
+    }
+
+    /// Moves the lease deadline of a lock held by `holder`.
+    pub fn renew(&mut self, holder: &str, expires_at: Option<SystemTime>) -> Result<(), Error> {
+        match &*self.state {
+            State::Unlocked => Err(Error::NotLocked),
+            State::Locked if self.holder.as_deref() == Some(holder) => {
+                self.expires_at = expires_at;
+                Ok(())
             }
+            State::Shared(readers) if readers.contains(holder) => {
+                self.expires_at = latest(self.expires_at, expires_at);
+                Ok(())
+            }
+            State::Counted(taken) if taken.contains_key(holder) => {
+                self.expires_at = latest(self.expires_at, expires_at);
+                Ok(())
+            }
+            _ => Err(Error::NotOwner(self.holders().join(", "))),
         }
     }
 
-    pub fn unlock(&mut self) -> Option<LockError> {
-        match self.locked() {
-            true => {
-                *Arc::make_mut(&mut self.state) = State::Unlocked;
-                None
+    /// Releases the lock if `holder` owns it. `force` skips the ownership check
+    /// and drops every reader of a shared lock and every semaphore permit.
+    ///
+    /// Semaphores give back `permits` of the holder's permits, 0 returns all of them.
+    /// Reentrant locks drop one hold and stay locked until the last one is gone.
+    pub fn unlock(&mut self, holder: &str, permits: u32, force: bool) -> Result<(), Error> {
+        match &*self.state {
+            State::Unlocked => Err(Error::NotLocked),
+            State::Locked if !force && self.holder.as_deref() != Some(holder) => {
+                Err(Error::NotOwner(self.holders().join(", ")))
+            }
+            State::Locked if !force && self.holds > 1 => {
+                self.holds -= 1;
+                Ok(())
+            }
+            State::Shared(readers) if !force && !readers.contains(holder) => {
+                Err(Error::NotOwner(self.holders().join(", ")))
+            }
+            State::Shared(readers) if !force && readers.len() > 1 => {
+                if let State::Shared(readers) = Arc::make_mut(&mut self.state) {
+                    readers.remove(holder);
+                }
+                self.stamps.remove(holder);
+                Ok(())
+            }
+            State::Counted(taken) if !force && !taken.contains_key(holder) => {
+                Err(Error::NotOwner(self.holders().join(", ")))
             }
-            false => Some(Error::msg("Already unlocked")),
+            State::Counted(taken)
+                if !force && (taken.len() > 1 || partial(permits, taken[holder])) =>
+            {
+                if let State::Counted(taken) = Arc::make_mut(&mut self.state) {
+                    let held = taken[holder];
+                    match partial(permits, held) {
+                        true => taken.insert(holder.to_string(), held - permits),
+                        false => {
+                            self.stamps.remove(holder);
+                            taken.remove(holder)
+                        }
+                    };
+                }
+                Ok(())
+            }
+            _ => {
+                self.release();
+                Ok(())
+            }
+        }
+    }
+
+    /// Leaves the lock unlocked, for all holders at once.
+    fn release(&mut self) {
+        *Arc::make_mut(&mut self.state) = State::Unlocked;
+        self.holder = None;
+        self.holds = 0;
+        self.subtree = false;
+        self.stamps.clear();
+        self.expires_at = None;
+        self.attached = Metadata::default();
+    }
+}
+
+/// The later of two lease deadlines, where `None` never expires.
+fn latest(current: Option<SystemTime>, other: Option<SystemTime>) -> Option<SystemTime> {
+    match (current, other) {
+        (Some(current), Some(other)) => Some(current.max(other)),
+        _ => None,
+    }
+}
+
+/// Whether giving back `permits` leaves some of the `held` permits taken.
+fn partial(permits: u32, held: u32) -> bool {
+    permits > 0 && permits < held
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn stamp(clock: u64, node: &str) -> Stamp {
+        Stamp {
+            clock,
+            node: node.to_string(),
+        }
+    }
+
+    fn exclusive(lock: &mut Lock, holder: &str) -> Result<(), Error> {
+        lock.lock(holder.to_string(), Mode::Exclusive, 0, None, 0)
+    }
+
+    #[test]
+    fn exclusive_lock_conflicts_until_released() {
+        let mut lock = Lock::new();
+        exclusive(&mut lock, "a").unwrap();
+        assert_eq!(lock.token(), 1);
+        assert!(matches!(
+            exclusive(&mut lock, "b"),
+            Err(Error::AlreadyLocked)
+        ));
+        assert!(matches!(
+            lock.unlock("b", 0, false),
+            Err(Error::NotOwner(_))
+        ));
+        lock.unlock("a", 0, false).unwrap();
+        assert!(!lock.locked());
+        assert!(matches!(lock.unlock("a", 0, false), Err(Error::NotLocked)));
+        exclusive(&mut lock, "b").unwrap();
+        assert_eq!(lock.token(), 2);
+    }
+
+    #[test]
+    fn force_unlock_drops_every_holder() {
+        let mut lock = Lock::new();
+        exclusive(&mut lock, "a").unwrap();
+        lock.unlock("b", 0, true).unwrap();
+        assert!(!lock.locked());
+    }
+
+    #[test]
+    fn readers_share_until_the_last_leaves() {
+        let mut lock = Lock::new();
+        lock.lock("a".to_string(), Mode::Shared, 0, None, 0)
+            .unwrap();
+        lock.lock("b".to_string(), Mode::Shared, 0, None, 0)
+            .unwrap();
+        assert_eq!(lock.holders(), vec!["a", "b"]);
+        assert!(matches!(
+            exclusive(&mut lock, "c"),
+            Err(Error::AlreadyLocked)
+        ));
+        lock.unlock("a", 0, false).unwrap();
+        assert!(lock.locked());
+        lock.unlock("b", 0, false).unwrap();
+        assert!(!lock.locked());
+    }
+
+    #[test]
+    fn semaphore_hands_out_its_permits() {
+        let mut lock = Lock::semaphore(3);
+        lock.lock("a".to_string(), Mode::Exclusive, 2, None, 0)
+            .unwrap();
+        assert!(matches!(
+            lock.lock("b".to_string(), Mode::Exclusive, 2, None, 0),
+            Err(Error::AlreadyLocked)
+        ));
+        lock.lock("b".to_string(), Mode::Exclusive, 1, None, 0)
+            .unwrap();
+        assert_eq!(lock.used(), 3);
+        lock.unlock("a", 1, false).unwrap();
+        assert_eq!(lock.used(), 2);
+        assert!(matches!(
+            lock.lock("c".to_string(), Mode::Exclusive, 4, None, 0),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn reentrant_lock_counts_holds() {
+        let mut lock = Lock::reentrant();
+        exclusive(&mut lock, "a").unwrap();
+        assert!(lock.reenter("a"));
+        assert!(!lock.reenter("b"));
+        assert_eq!(lock.holds(), 2);
+        assert_eq!(lock.token(), 1);
+        lock.unlock("a", 0, false).unwrap();
+        assert!(lock.locked());
+        lock.unlock("a", 0, false).unwrap();
+        assert!(!lock.locked());
+        assert!(!Lock::new().reenter("a"));
+    }
+
+    #[test]
+    fn lease_expires_at_its_deadline() {
+        let mut lock = Lock::new();
+        lock.lock("a".to_string(), Mode::Exclusive, 0, Some(at(10)), 0)
+            .unwrap();
+        assert!(!lock.expired(at(9)));
+        assert!(lock.expired(at(10)));
+        assert!(matches!(
+            lock.renew("b", Some(at(20))),
+            Err(Error::NotOwner(_))
+        ));
+        lock.renew("a", Some(at(20))).unwrap();
+        assert!(!lock.expired(at(10)));
+        lock.renew("a", None).unwrap();
+        assert!(!lock.expired(at(u32::MAX as u64)));
+    }
+
+    #[test]
+    fn readers_share_the_latest_lease() {
+        let mut lock = Lock::new();
+        lock.lock("a".to_string(), Mode::Shared, 0, Some(at(10)), 0)
+            .unwrap();
+        lock.lock("b".to_string(), Mode::Shared, 0, Some(at(20)), 0)
+            .unwrap();
+        assert_eq!(lock.expires_at(), Some(at(20)));
+    }
+
+    #[test]
+    fn waiters_line_up_by_arrival_then_name() {
+        let mut lock = Lock::new();
+        assert_eq!(lock.enqueue("c".to_string(), at(2), None), 1);
+        assert_eq!(lock.enqueue("b".to_string(), at(1), None), 1);
+        assert_eq!(lock.enqueue("a".to_string(), at(2), None), 2);
+        assert_eq!(lock.enqueue("b".to_string(), at(5), None), 1);
+        assert_eq!(lock.waiters(), vec!["b", "a", "c"]);
+        assert!(matches!(lock.in_turn("a"), Err(Error::AlreadyLocked)));
+        lock.in_turn("b").unwrap();
+        exclusive(&mut lock, "b").unwrap();
+        assert_eq!(lock.waiters(), vec!["a", "c"]);
+        assert!(lock.dequeue("c"));
+        assert!(!lock.dequeue("c"));
+        assert_eq!(lock.position("a"), Some(1));
+    }
+
+    #[test]
+    fn waiters_lapse_at_their_deadline() {
+        let mut lock = Lock::new();
+        lock.enqueue("a".to_string(), at(1), Some(at(10)));
+        lock.enqueue("b".to_string(), at(2), None);
+        assert!(lock.lapsed(at(9)).is_empty());
+        assert_eq!(lock.lapsed(at(10)), vec!["a"]);
+        // Waiting again only moves the deadline, not the place in the queue.
+        assert_eq!(lock.enqueue("a".to_string(), at(5), Some(at(20))), 1);
+        assert_eq!(lock.waiting_since("a"), Some(at(1)));
+        assert!(lock.lapsed(at(10)).is_empty());
+        assert!(lock.lapsed(at(1000)).contains(&String::from("a")));
+        assert!(!lock.lapsed(at(1000)).contains(&String::from("b")));
+    }
+
+    #[test]
+    fn earlier_acquisition_wins_a_contest() {
+        let mut lock = Lock::new();
+        exclusive(&mut lock, "a").unwrap();
+        lock.stamped("a", stamp(2, "n1"));
+        assert_eq!(
+            lock.contest("b", Mode::Exclusive, 0, &stamp(3, "n2")),
+            Err(String::from("a"))
+        );
+        // Equal clocks are settled by the node address.
+        assert_eq!(
+            lock.contest("b", Mode::Exclusive, 0, &stamp(2, "n0")),
+            Ok(vec![String::from("a")])
+        );
+        lock.evict("a");
+        assert!(!lock.locked());
+        assert_eq!(lock.stamp(), None);
+    }
+
+    #[test]
+    fn contest_evicts_only_the_permits_it_needs() {
+        let mut lock = Lock::semaphore(2);
+        for (holder, clock) in [("a", 1), ("b", 5)] {
+            lock.lock(holder.to_string(), Mode::Exclusive, 1, None, 0)
+                .unwrap();
+            lock.stamped(holder, stamp(clock, "n1"));
         }
+        assert_eq!(
+            lock.contest("c", Mode::Exclusive, 1, &stamp(3, "n2")),
+            Ok(vec![String::from("b")])
+        );
+        assert_eq!(
+            lock.contest("c", Mode::Exclusive, 2, &stamp(3, "n2")),
+            Err(String::from("a"))
+        );
+        lock.evict("b");
+        assert_eq!(lock.holders(), vec!["a"]);
+    }
+
+    #[test]
+    fn readers_do_not_contest_each_other() {
+        let mut lock = Lock::new();
+        lock.lock("a".to_string(), Mode::Shared, 0, None, 0)
+            .unwrap();
+        lock.stamped("a", stamp(5, "n1"));
+        assert_eq!(
+            lock.contest("b", Mode::Shared, 0, &stamp(1, "n2")),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn tokens_never_go_back() {
+        let mut lock = Lock::new();
+        lock.lock("a".to_string(), Mode::Exclusive, 0, None, 7)
+            .unwrap();
+        assert_eq!(lock.token(), 7);
+        lock.fence(3);
+        assert_eq!(lock.token(), 7);
+        lock.unlock("a", 0, false).unwrap();
+        lock.lock("b".to_string(), Mode::Exclusive, 0, None, 3)
+            .unwrap();
+        assert_eq!(lock.token(), 8);
+        lock.fence(9);
+        assert_eq!(lock.token(), 9);
+    }
+
+    #[test]
+    fn attachments_go_with_the_holder() {
+        let mut lock = Lock::new();
+        lock.annotate(Metadata {
+            description: String::from("kept"),
+            ..Default::default()
+        });
+        exclusive(&mut lock, "a").unwrap();
+        lock.attach(Metadata {
+            payload: vec![1],
+            ..Default::default()
+        });
+        assert_eq!(lock.metadata().payload, vec![1]);
+        lock.unlock("a", 0, false).unwrap();
+        assert!(lock.attachments().is_empty());
+        assert_eq!(lock.metadata().description, "kept");
+    }
+
+    #[test]
+    fn payload_is_capped() {
+        let metadata = Metadata {
+            payload: vec![0; MAX_PAYLOAD + 1],
+            ..Default::default()
+        };
+        assert!(matches!(metadata.check(), Err(Error::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        let mut lock = Lock::new();
+        lock.lock("a".to_string(), Mode::Exclusive, 0, None, 0)
+            .unwrap();
+        lock.enqueue("b".to_string(), at(1), None);
+        let mut other = lock.clone();
+        assert_eq!(lock.fingerprint(), other.fingerprint());
+        // Peers compare it, so it must not change between builds.
+        assert_eq!(lock.fingerprint(), 14058815365484834983);
+        other.dequeue("b");
+        assert_ne!(lock.fingerprint(), other.fingerprint());
     }
 }
@@ -1 +1,4 @@
+pub mod barrier;
+pub mod latch;
 pub mod lock;
+pub mod namespace;
@@ -0,0 +1,59 @@
+//! Lock names are `/`-separated paths, each path is the namespace of the
+//! locks below it.
+
+pub const SEPARATOR: char = '/';
+
+/// Whether `name` is `namespace` itself or lies below it. The empty
+/// namespace contains every name.
+pub fn contains(namespace: &str, name: &str) -> bool {
+    let namespace = namespace.trim_end_matches(SEPARATOR);
+    if namespace.is_empty() {
+        return true;
+    }
+    match name.strip_prefix(namespace) {
+        Some(rest) => rest.is_empty() || rest.starts_with(SEPARATOR),
+        None => false,
+    }
+}
+
+/// Whether one of the names lies below the other or both are the same.
+pub fn related(name: &str, other: &str) -> bool {
+    contains(name, other) || contains(other, name)
+}
+
+/// Paths above `name`, the closest first.
+pub fn parents(name: &str) -> impl Iterator<Item = &str> {
+    name.rmatch_indices(SEPARATOR)
+        .map(move |(index, _)| &name[..index])
+        .filter(|parent| !parent.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespace_contains_itself_and_names_below() {
+        assert!(contains("a/b", "a/b"));
+        assert!(contains("a/b", "a/b/c"));
+        assert!(contains("a/b/", "a/b/c"));
+        assert!(!contains("a/b", "a/bc"));
+        assert!(!contains("a/b", "a"));
+        assert!(contains("", "a"));
+        assert!(contains("/", "a"));
+    }
+
+    #[test]
+    fn related_names_are_on_one_path() {
+        assert!(related("a", "a/b"));
+        assert!(related("a/b", "a"));
+        assert!(!related("a/b", "a/c"));
+    }
+
+    #[test]
+    fn parents_start_with_the_closest() {
+        assert_eq!(parents("a/b/c").collect::<Vec<_>>(), vec!["a/b", "a"]);
+        assert_eq!(parents("/a/b").collect::<Vec<_>>(), vec!["/a"]);
+        assert_eq!(parents("a").count(), 0);
+    }
+}
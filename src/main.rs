@@ -7,6 +7,7 @@ mod app;
 mod cli;
 mod client;
 mod config;
+mod error;
 mod handler;
 mod lock;
 mod proto;
@@ -7,11 +7,137 @@ pub mod api {
     tonic::include_proto!("api.grpc");
 }
 
+pub mod raft {
+    tonic::include_proto!("raft.grpc");
+}
+
 pub use api::{
-    list_response::Lock,
     lock_event::Status,
     locking_client::LockingClient,
     locking_server::{Locking, LockingServer},
     peers_response::Peer,
-    Empty, ListResponse, LockEvent, LockRequest, LockResponse, PeersResponse,
+    BarrierRequest, BarrierResponse, ElectionRequest, Empty, LatchRequest, LatchResponse,
+    LeaderResponse, ListRequest, ListResponse, LockEvent, LockManyRequest, LockManyResponse,
+    LockRequest, LockResponse, PeersResponse, WatchRequest,
 };
+
+impl swarm::swarm_message::Payload {
+    /// Random id the message was gossiped with, `None` for acknowledgements
+    /// which carry the id of the acquisition they answer.
+    pub fn message_id(&self) -> Option<&str> {
+        use swarm::swarm_message::Payload;
+        match self {
+            Payload::LockMessage(msg) => Some(&msg.message_id),
+            Payload::LockBatch(batch) => Some(&batch.message_id),
+            Payload::BarrierMessage(msg) => Some(&msg.message_id),
+            Payload::LatchMessage(msg) => Some(&msg.message_id),
+            Payload::Ack(_) => None,
+            Payload::Digest(digest) => Some(&digest.message_id),
+            Payload::Pull(pull) => Some(&pull.message_id),
+            Payload::Snapshot(snapshot) => Some(&snapshot.message_id),
+        }
+    }
+}
+
+impl From<api::Mode> for crate::lock::lock::Mode {
+    fn from(mode: api::Mode) -> Self {
+        match mode {
+            api::Mode::Exclusive => Self::Exclusive,
+            api::Mode::Shared => Self::Shared,
+        }
+    }
+}
+
+impl From<crate::lock::lock::Mode> for api::Mode {
+    fn from(mode: crate::lock::lock::Mode) -> Self {
+        match mode {
+            crate::lock::lock::Mode::Exclusive => Self::Exclusive,
+            crate::lock::lock::Mode::Shared => Self::Shared,
+        }
+    }
+}
+
+impl From<swarm::lock_message::Mode> for crate::lock::lock::Mode {
+    fn from(mode: swarm::lock_message::Mode) -> Self {
+        match mode {
+            swarm::lock_message::Mode::Exclusive => Self::Exclusive,
+            swarm::lock_message::Mode::Shared => Self::Shared,
+        }
+    }
+}
+
+impl From<crate::lock::lock::Mode> for swarm::lock_message::Mode {
+    fn from(mode: crate::lock::lock::Mode) -> Self {
+        match mode {
+            crate::lock::lock::Mode::Exclusive => Self::Exclusive,
+            crate::lock::lock::Mode::Shared => Self::Shared,
+        }
+    }
+}
+
+impl From<api::Metadata> for crate::lock::lock::Metadata {
+    fn from(metadata: api::Metadata) -> Self {
+        Self {
+            labels: metadata.labels.into_iter().collect(),
+            description: metadata.description,
+            payload: metadata.payload,
+        }
+    }
+}
+
+impl From<crate::lock::lock::Metadata> for api::Metadata {
+    fn from(metadata: crate::lock::lock::Metadata) -> Self {
+        Self {
+            labels: metadata.labels.into_iter().collect(),
+            description: metadata.description,
+            payload: metadata.payload,
+        }
+    }
+}
+
+impl From<swarm::Metadata> for crate::lock::lock::Metadata {
+    fn from(metadata: swarm::Metadata) -> Self {
+        Self {
+            labels: metadata.labels.into_iter().collect(),
+            description: metadata.description,
+            payload: metadata.payload,
+        }
+    }
+}
+
+impl From<crate::lock::lock::Metadata> for swarm::Metadata {
+    fn from(metadata: crate::lock::lock::Metadata) -> Self {
+        Self {
+            labels: metadata.labels.into_iter().collect(),
+            description: metadata.description,
+            payload: metadata.payload,
+        }
+    }
+}
+
+impl From<crate::handler::event::Stamped> for LockEvent {
+    fn from(stamped: crate::handler::event::Stamped) -> Self {
+        match stamped.event {
+            crate::handler::event::Event::Deadlock(deadlock) => Self {
+                name: deadlock.name,
+                status: Status::Deadlock.into(),
+                holder: deadlock.holder,
+                description: deadlock.description,
+                revision: stamped.revision,
+            },
+            crate::handler::event::Event::Lost(conflict) => Self {
+                description: format!("Lost {} to {:?}", conflict.name, conflict.winner),
+                name: conflict.name,
+                status: Status::Lost.into(),
+                holder: conflict.holder,
+                revision: stamped.revision,
+            },
+            event => Self {
+                name: event.name().to_string(),
+                status: event.status().into(),
+                revision: stamped.revision,
+                ..Default::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::Error;
+use crate::handler::Handler;
+use crate::lock::lock::Lock;
+use crate::proto::swarm::{lock_message::Action, swarm_message::Payload, LockMessage};
+use crate::storage::traits::Storage;
+use crate::swarm::Raft;
+use std::time::Duration;
+
+pub const DETECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Periodically looks for deadlocks between blocked Acquire calls.
+///
+/// Every node runs its own detector over the gossiped wait queues and picks
+/// the same victim, the waiter that queued last, so only the node serving
+/// that Acquire actually aborts a call. In raft mode only the leader, which
+/// serves every Acquire, detects them and dequeues the victims through the
+/// log.
+pub async fn serve<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
+    handler: Handler<S>,
+    raft: Option<Raft<S>>,
+    interval: Duration,
+) -> Result<(), anyhow::Error> {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        let detected = match &raft {
+            Some(raft) if raft.leading().is_err() => continue,
+            Some(raft) => detect_committed(&handler, raft).await,
+            None => handler.detect().map(|_| ()),
+        };
+        if let Err(err) = detected {
+            warn!("Could not detect deadlocks: {}", err);
+        }
+    }
+}
+
+async fn detect_committed<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
+    handler: &Handler<S>,
+    raft: &Raft<S>,
+) -> Result<(), Error> {
+    for deadlock in handler.deadlocks()? {
+        handler.abort(&deadlock);
+        raft.propose(Payload::LockMessage(LockMessage {
+            name: deadlock.name,
+            action: Action::Dequeued.into(),
+            message_id: nano_id::base64::<21>(),
+            holder: deadlock.holder,
+            ..Default::default()
+        }))
+        .await?;
+    }
+    Ok(())
+}
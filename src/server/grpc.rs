@@ -1,29 +1,521 @@
-use crate::lock::lock::Lock;
+use crate::error::Error;
+use crate::handler::{event, handler::Applied, Handler};
+use crate::lock::lock::{self, Lock};
+use crate::lock::namespace;
 use crate::proto::swarm::{
-    lock_message::Action, swarm_message::Payload, LockMessage, SwarmMessage,
+    barrier_message, latch_message, lock_message, lock_message::Action, swarm_message::Payload,
+    Ack, BarrierMessage, LatchMessage, LockBatch, LockMessage, SwarmMessage,
 };
 use crate::proto::{
-    api::list_response, api::lock_event, Empty, ListResponse, LockEvent, LockRequest, LockResponse,
-    Locking, LockingServer, Peer, PeersResponse,
+    api::list_request, api::list_response, api::lock_response, api::Mode, BarrierRequest,
+    BarrierResponse, ElectionRequest, Empty, LatchRequest, LatchResponse, LeaderResponse,
+    ListRequest, ListResponse, LockEvent, LockManyRequest, LockManyResponse, LockRequest,
+    LockResponse, Locking, LockingServer, Peer, PeersResponse, WatchRequest,
 };
 use crate::storage::traits::Storage;
-use crate::swarm::Swarm;
+use crate::swarm::{Raft, Swarm};
 use crate::util::*;
-use crate::{handler::event, handler::Handler};
-use futures::Stream;
+use futures::{Future, Stream, TryStreamExt};
 use prost::Message;
 use std::{
+    collections::HashSet,
     pin::Pin,
-    sync::{Arc, Mutex},
-    time::Duration,
+    sync::{mpsc, Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant, SystemTime},
 };
-use tokio_stream::StreamExt;
-use tonic::{transport::Server, Request, Response, Status};
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+use tonic::{transport::Server, Request, Response, Status, Streaming};
 use tower_http::trace::TraceLayer;
 
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Routes that stream or block for as long as the caller asks, the request
+/// timeout does not apply to them.
+const UNLIMITED: &[&str] = &[
+    "/api.grpc.Locking/KeepAliveStream",
+    "/api.grpc.Locking/Watch",
+    "/api.grpc.Locking/Observe",
+    "/api.grpc.Locking/Acquire",
+    "/api.grpc.Locking/AcquireMany",
+    "/api.grpc.Locking/Campaign",
+    "/api.grpc.Locking/Enter",
+    "/api.grpc.Locking/Wait",
+];
+/// Locks per List page if the request does not ask for a size.
+const DEFAULT_PAGE_SIZE: u32 = 100;
+const MAX_PAGE_SIZE: u32 = 1000;
+/// How long a waiter stays queued unless its Acquire renews its place, so
+/// waiters of a node that went away do not hold the queue up for good.
+const QUEUE_LEASE: Duration = Duration::from_secs(30);
+/// How long a lock waits for the quorum to acknowledge it, gossip takes a
+/// few rounds there and back.
+const ACK_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct Locker<S: Storage<String, Lock> + Clone + Send + 'static> {
     handler: Handler<S>,
     swarm: Arc<Mutex<Swarm<Handler<S>>>>,
+    /// Replaces gossip in raft mode.
+    raft: Option<Raft<S>>,
+    /// Peers that acknowledge a lock in gossip mode before it is granted.
+    quorum: usize,
+    /// Cluster addresses of the peers, only their acks count.
+    peers: HashSet<String>,
+    /// Whether Unlock may release locks held by someone else.
+    allow_force: bool,
+}
+
+impl<S> Locker<S>
+where
+    S: Storage<String, Lock> + Clone + Sync + Send + 'static,
+{
+    /// Gossips `message`, stamped with the next tick of the Lamport clock
+    /// unless it already carries one.
+    fn broadcast(&self, mut message: LockMessage) -> Result<(), Error> {
+        if message.node.is_empty() {
+            let stamp = self.handler.tick();
+            message.clock = stamp.clock;
+            message.node = stamp.node;
+        }
+        self.gossip(Payload::LockMessage(message))
+    }
+
+    /// Gossips `payload` the node already applied.
+    fn gossip(&self, payload: Payload) -> Result<(), Error> {
+        let msg = SwarmMessage {
+            payload: payload.into(),
+        };
+        let mut buffer = vec![];
+        msg.encode(&mut buffer)
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        // Echoes of our own messages are dropped as duplicates.
+        if let Some(message_id) = msg.payload.as_ref().and_then(Payload::message_id) {
+            self.handler.remember(message_id)?;
+        }
+        self.swarm.lock().unwrap().message(buffer)
+    }
+
+    /// Proposes `payload` to the raft log, nothing changes before it
+    /// commits. Returns what applying it came to.
+    async fn propose(&self, payload: Payload) -> Result<Applied, Error> {
+        match &self.raft {
+            Some(raft) => raft.propose(payload).await,
+            None => Err(Error::Internal(String::from("Not in raft mode"))),
+        }
+    }
+
+    /// Proposes `payload` without waiting for it to commit, for the guards
+    /// that clean up when a call returns.
+    fn submit(&self, payload: Payload) -> Result<(), Error> {
+        let raft = match &self.raft {
+            Some(raft) => raft.clone(),
+            None => return Err(Error::Internal(String::from("Not in raft mode"))),
+        };
+        tokio::spawn(async move {
+            if let Err(err) = raft.propose(payload).await {
+                debug!("Could not propose clean-up: {}", err);
+            }
+        });
+        Ok(())
+    }
+
+    /// Fails in raft mode unless this node leads, only the leader changes
+    /// anything.
+    fn leading(&self) -> Result<(), Error> {
+        match &self.raft {
+            Some(raft) => raft.leading(),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails if `holder` has a reentrant lock handed out by another peer,
+    /// which is the only one counting its holds. Raft replicates them.
+    fn counting_holds(&self, name: &str, holder: &str) -> Result<(), Error> {
+        if self.raft.is_some() {
+            return Ok(());
+        }
+        let lock = match self.handler.get(name.to_string()) {
+            Ok(lock) => lock,
+            Err(Error::NotFound(_)) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        match lock.stamp() {
+            Some(stamp)
+                if lock.is_reentrant()
+                    && lock.holder().as_deref() == Some(holder)
+                    && !self.handler.handed_out_here(&lock) =>
+            {
+                Err(Error::InvalidArgument(format!(
+                    "{:?} holds {} through {}, take and release it there",
+                    holder, name, stamp.node
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Acquires the lock locally and gossips it, returning the fencing token
+    /// or `None` if the lock is held.
+    async fn try_lock(&self, request: LockRequest) -> Result<Option<u64>, Error> {
+        require_holder(&request.holder)?;
+        let metadata: lock::Metadata = request.metadata.clone().unwrap_or_default().into();
+        metadata.check()?;
+        let expires_at = deadline_from_ttl(request.ttl);
+        let mode: lock::Mode = Mode::from_i32(request.mode)
+            .unwrap_or(Mode::Exclusive)
+            .into();
+        if request.subtree && mode == lock::Mode::Shared {
+            return Err(Error::InvalidArgument(String::from(
+                "Subtrees can only be locked exclusively",
+            )));
+        }
+        let stamp = self.handler.tick();
+        let message = LockMessage {
+            name: request.name.clone(),
+            action: Action::Locked.into(),
+            metadata: Some(metadata.clone().into()),
+            message_id: nano_id::base64::<21>(),
+            holder: request.holder.clone(),
+            expires_at: millis_from_time(expires_at),
+            mode: lock_message::Mode::from(mode).into(),
+            permits: request.permits,
+            subtree: request.subtree,
+            clock: stamp.clock,
+            node: stamp.node.clone(),
+            ..Default::default()
+        };
+        if self.raft.is_some() {
+            return match self.propose(Payload::LockMessage(message)).await {
+                Ok(Applied::Token(token)) => Ok(Some(token)),
+                Ok(applied) => Err(unexpected(applied)),
+                Err(Error::AlreadyLocked) => Ok(None),
+                Err(err) => Err(err),
+            };
+        }
+        // Holds only count on the peer that handed them out, gossip carries
+        // the first acquisition and the final release.
+        if mode == lock::Mode::Exclusive {
+            self.counting_holds(&request.name, &request.holder)?;
+            if let Some(token) = self
+                .handler
+                .reentered(request.name.clone(), request.holder.clone())?
+            {
+                return Ok(Some(token));
+            }
+        }
+        let locked = match request.subtree {
+            true => self.handler.locked_subtree(
+                request.name.clone(),
+                request.holder.clone(),
+                expires_at,
+                stamp.clone(),
+            ),
+            false => self.handler.locked(
+                request.name.clone(),
+                request.holder.clone(),
+                mode,
+                request.permits,
+                expires_at,
+                stamp.clone(),
+            ),
+        };
+        let token = match locked {
+            Ok(token) => token,
+            Err(Error::AlreadyLocked) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        self.handler
+            .attached(request.name.clone(), metadata.clone())?;
+        let message_id = message.message_id.clone();
+        let acks = match self.quorum {
+            0 => None,
+            _ => Some(self.handler.expect_acks(message_id.clone())?),
+        };
+        let broadcast = self.broadcast(LockMessage {
+            token,
+            ack: acks.is_some(),
+            ..message
+        });
+        if let Err(err) = broadcast {
+            // Nobody else saw the lock, so nobody may hold it.
+            self.handler.forget_acks(&message_id)?;
+            self.handler
+                .unlocked(request.name, request.holder, request.permits, false)?;
+            return Err(err);
+        }
+        let acked = match acks {
+            Some(acks) => self.acknowledged(&message_id, acks),
+            None => Ok(true),
+        };
+        match acked {
+            Ok(true) => Ok(Some(token)),
+            Ok(false) => {
+                self.retract(request.name, request.holder, request.permits)?;
+                Ok(None)
+            }
+            Err(err) => {
+                self.retract(request.name, request.holder, request.permits)?;
+                Err(err)
+            }
+        }
+    }
+
+    /// Waits until `quorum` peers took over the acquisition gossiped as
+    /// `message_id`, see `tally`.
+    fn acknowledged(&self, message_id: &str, acks: mpsc::Receiver<Ack>) -> Result<bool, Error> {
+        let acked = tokio::task::block_in_place(|| {
+            tally(message_id, &acks, &self.peers, self.quorum, ACK_TIMEOUT)
+        });
+        self.handler.forget_acks(message_id)?;
+        acked
+    }
+
+    /// Gives back an acquisition the peers did not acknowledge, also on the
+    /// peers that did.
+    fn retract(&self, name: String, holder: String, permits: u32) -> Result<(), Error> {
+        match self
+            .handler
+            .unlocked(name.clone(), holder.clone(), permits, false)
+        {
+            // The acquisition that won may have taken it over already.
+            Ok(_) | Err(Error::NotOwner(_)) | Err(Error::NotLocked) => {}
+            Err(err) => return Err(err),
+        }
+        self.broadcast(LockMessage {
+            name,
+            action: Action::Unlocked.into(),
+            message_id: nano_id::base64::<21>(),
+            holder,
+            permits,
+            ..Default::default()
+        })
+    }
+
+    /// Acquires all requested locks locally and gossips them as one batch,
+    /// returning their fencing tokens or `None` if any of them is held.
+    async fn try_lock_many(&self, request: LockManyRequest) -> Result<Option<Vec<u64>>, Error> {
+        if request.names.is_empty() {
+            return Err(Error::InvalidArgument(String::from("No locks requested")));
+        }
+        require_holder(&request.holder)?;
+        let expires_at = deadline_from_ttl(request.ttl);
+        let stamp = self.handler.tick();
+        let batch = |tokens: &[u64]| LockBatch {
+            message_id: nano_id::base64::<21>(),
+            locks: request
+                .names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| LockMessage {
+                    name: name.clone(),
+                    action: Action::Locked.into(),
+                    message_id: nano_id::base64::<21>(),
+                    holder: request.holder.clone(),
+                    expires_at: millis_from_time(expires_at),
+                    token: tokens.get(index).copied().unwrap_or_default(),
+                    clock: stamp.clock,
+                    node: stamp.node.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+        };
+        if self.raft.is_some() {
+            return match self.propose(Payload::LockBatch(batch(&[]))).await {
+                Ok(Applied::Tokens(tokens)) => Ok(Some(tokens)),
+                Ok(applied) => Err(unexpected(applied)),
+                Err(Error::AlreadyLocked) => Ok(None),
+                Err(err) => Err(err),
+            };
+        }
+        let tokens = match self.handler.locked_many(
+            request.holder.clone(),
+            expires_at,
+            request.names.clone(),
+            stamp.clone(),
+        ) {
+            Ok(tokens) => tokens,
+            Err(Error::AlreadyLocked) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let gossip = self.gossip(Payload::LockBatch(batch(&tokens)));
+        if let Err(err) = gossip {
+            for name in request.names {
+                self.handler
+                    .unlocked(name, request.holder.clone(), 0, false)?;
+            }
+            return Err(err);
+        }
+        Ok(Some(tokens))
+    }
+
+    /// Lines `holder` up for the lock and gossips its place in the queue,
+    /// which it keeps until `deadline` but at most for `QUEUE_LEASE`. Holders
+    /// already waiting keep their place and only move that deadline.
+    async fn enqueue(
+        &self,
+        name: &str,
+        holder: &str,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<(), Error> {
+        let left = deadline.map_or(QUEUE_LEASE, |deadline| {
+            deadline
+                .saturating_duration_since(tokio::time::Instant::now())
+                .min(QUEUE_LEASE)
+        });
+        // Whole milliseconds like peers get it, so they order the queue alike.
+        let since = time_from_millis(millis_from_time(Some(SystemTime::now())))
+            .unwrap_or_else(SystemTime::now);
+        let until = time_from_millis(millis_from_time(Some(since + left)));
+        let message = LockMessage {
+            name: name.to_string(),
+            action: Action::Queued.into(),
+            message_id: nano_id::base64::<21>(),
+            holder: holder.to_string(),
+            queued_at: millis_from_time(Some(since)),
+            wait_until: millis_from_time(until),
+            ..Default::default()
+        };
+        if self.raft.is_some() {
+            return self
+                .propose(Payload::LockMessage(message))
+                .await
+                .map(|_| ());
+        }
+        let position = self
+            .handler
+            .queued(name.to_string(), holder.to_string(), since, until)?;
+        debug!("{:?} waits for {} at {}", holder, name, position);
+        self.broadcast(message)
+    }
+
+    fn dequeue(&self, name: &str, holder: &str) -> Result<(), Error> {
+        let message = LockMessage {
+            name: name.to_string(),
+            action: Action::Dequeued.into(),
+            message_id: nano_id::base64::<21>(),
+            holder: holder.to_string(),
+            ..Default::default()
+        };
+        if self.raft.is_some() {
+            if self
+                .handler
+                .position(name.to_string(), holder.to_string())?
+                == 0
+            {
+                return Ok(());
+            }
+            return self.submit(Payload::LockMessage(message));
+        }
+        if !self
+            .handler
+            .dequeued(name.to_string(), holder.to_string())?
+        {
+            return Ok(());
+        }
+        self.broadcast(message)
+    }
+
+    /// Takes `participant` back out of a barrier that has not tripped yet.
+    fn leave(&self, name: &str, participant: &str, generation: u64) -> Result<(), Error> {
+        let message = Payload::BarrierMessage(BarrierMessage {
+            name: name.to_string(),
+            action: barrier_message::Action::Left.into(),
+            message_id: nano_id::base64::<21>(),
+            participant: participant.to_string(),
+            generation,
+            ..Default::default()
+        });
+        if self.raft.is_some() {
+            if self.handler.barrier(name.to_string())?.generation() != generation {
+                return Ok(());
+            }
+            return self.submit(message);
+        }
+        if !self
+            .handler
+            .left(name.to_string(), participant.to_string(), generation)?
+        {
+            return Ok(());
+        }
+        self.gossip(message)
+    }
+
+    fn barrier(&self, name: String) -> Result<BarrierResponse, Error> {
+        let barrier = self.handler.barrier(name.clone())?;
+        Ok(BarrierResponse {
+            name,
+            parties: barrier.parties(),
+            arrived: barrier.arrived(),
+            generation: barrier.generation(),
+        })
+    }
+
+    async fn renew(&self, request: LockRequest) -> Result<(), Error> {
+        if request.ttl == 0 {
+            return Err(Error::InvalidArgument(String::from(
+                "KeepAlive requires a ttl",
+            )));
+        }
+        let expires_at = deadline_from_ttl(request.ttl);
+        let message = LockMessage {
+            name: request.name.clone(),
+            action: Action::Renewed.into(),
+            message_id: nano_id::base64::<21>(),
+            holder: request.holder.clone(),
+            expires_at: millis_from_time(expires_at),
+            ..Default::default()
+        };
+        if self.raft.is_some() {
+            return self
+                .propose(Payload::LockMessage(message))
+                .await
+                .map(|_| ());
+        }
+        self.handler
+            .renewed(request.name, request.holder, expires_at)?;
+        self.broadcast(message)
+    }
+}
+
+/// Takes a blocked acquirer out of the wait queue once its Acquire returns,
+/// also when the caller hung up.
+struct Waiting<'a, S: Storage<String, Lock> + Clone + Sync + Send + 'static> {
+    locker: &'a Locker<S>,
+    name: String,
+    holder: String,
+}
+
+impl<S> Drop for Waiting<'_, S>
+where
+    S: Storage<String, Lock> + Clone + Sync + Send + 'static,
+{
+    fn drop(&mut self) {
+        if let Err(err) = self.locker.dequeue(&self.name, &self.holder) {
+            debug!("Could not leave queue of {}: {}", self.name, err);
+        }
+    }
+}
+
+/// Takes a participant back out of a barrier if its Enter returns before the
+/// barrier tripped, also when the caller hung up.
+struct Arrival<'a, S: Storage<String, Lock> + Clone + Sync + Send + 'static> {
+    locker: &'a Locker<S>,
+    name: String,
+    participant: String,
+    generation: u64,
+}
+
+impl<S> Drop for Arrival<'_, S>
+where
+    S: Storage<String, Lock> + Clone + Sync + Send + 'static,
+{
+    fn drop(&mut self) {
+        if let Err(err) = self
+            .locker
+            .leave(&self.name, &self.participant, self.generation)
+        {
+            debug!("Could not leave barrier {}: {}", self.name, err);
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -33,18 +525,27 @@ where
 {
     type WatchStream =
         Pin<Box<dyn Stream<Item = Result<LockEvent, Status>> + Send + Sync + 'static>>;
+    type ObserveStream =
+        Pin<Box<dyn Stream<Item = Result<LeaderResponse, Status>> + Send + Sync + 'static>>;
 
     async fn state(&self, request: Request<LockRequest>) -> Result<Response<LockResponse>, Status> {
+        let request = request.into_inner();
         Ok(Response::new(LockResponse {
-            body: Some(crate::proto::api::lock_response::Body::State(
-                match self.handler.state(request.into_inner().name) {
-                    Ok(r) => r,
-                    Err(err) => return Err(Status::new(tonic::Code::Internal, err.to_string())),
-                },
+            body: Some(lock_response::Body::State(
+                self.handler.state(request.name.clone())?,
             )),
+            position: self
+                .handler
+                .position(request.name.clone(), request.holder)? as u32,
+            metadata: Some(self.handler.get(request.name)?.metadata().into()),
         }))
     }
     async fn peers(&self, _request: Request<Empty>) -> Result<Response<PeersResponse>, Status> {
+        if let Some(raft) = &self.raft {
+            return Ok(Response::new(PeersResponse {
+                peers: raft.peers()?,
+            }));
+        }
         let mut peer_inc: u16 = 0;
         let peers = self
             .swarm
@@ -64,33 +565,78 @@ where
         &self,
         request: Request<LockRequest>,
     ) -> Result<Response<LockResponse>, Status> {
-        let lock_name = request.into_inner().name;
-        info!("Creating lock: {}", lock_name);
-        self.handler.created(lock_name.clone());
-        let msg = SwarmMessage {
-            payload: Payload::LockMessage(LockMessage {
-                name: lock_name,
-                action: Action::Created.into(),
-                message_id: nano_id::base64::<21>(),
-            })
-            .into(),
+        self.leading()?;
+        let request = request.into_inner();
+        info!("Creating lock: {}", request.name);
+        let metadata: lock::Metadata = request.metadata.unwrap_or_default().into();
+        metadata.check()?;
+        let message = LockMessage {
+            metadata: Some(metadata.clone().into()),
+            name: request.name.clone(),
+            action: Action::Created.into(),
+            message_id: nano_id::base64::<21>(),
+            permits: request.permits,
+            reentrant: request.reentrant,
+            ..Default::default()
         };
-        let mut buffer = vec![];
-        msg.encode(&mut buffer).unwrap();
-        self.swarm.lock().unwrap().message(buffer).unwrap();
+        if self.raft.is_some() {
+            self.propose(Payload::LockMessage(message)).await?;
+            return Ok(Response::new(LockResponse::default()));
+        }
+        self.handler
+            .created(request.name.clone(), request.permits, request.reentrant)?;
+        self.handler.annotated(request.name, metadata)?;
+        self.broadcast(message)?;
         Ok(Response::new(LockResponse::default()))
     }
 
-    async fn list(&self, _: Request<Empty>) -> Result<Response<ListResponse>, Status> {
+    async fn list(&self, request: Request<ListRequest>) -> Result<Response<ListResponse>, Status> {
+        let request = request.into_inner();
+        let state = match list_request::StateFilter::from_i32(request.state) {
+            Some(list_request::StateFilter::Locked) => Some(true),
+            Some(list_request::StateFilter::Unlocked) => Some(false),
+            _ => None,
+        };
+        let page_size = match request.page_size {
+            0 => DEFAULT_PAGE_SIZE,
+            page_size => page_size.min(MAX_PAGE_SIZE),
+        };
+        // Pages continue after the last name of the previous one.
+        let after = match request.page_token.as_str() {
+            "" => None,
+            token => Some(token.to_string()),
+        };
+        // Read before the locks, so events after it cover every later change.
+        let revision = self.handler.revision()?;
+        let (locks, more) =
+            self.handler
+                .list_page(&request.prefix, state, after, page_size as usize)?;
         Ok(Response::new(ListResponse {
-            locks: self
-                .handler
-                .list()
-                .map_err(|err| Status::new(tonic::Code::Internal, err.to_string()))?
+            revision,
+            next_page_token: match (more, locks.last()) {
+                (true, Some((name, _))) => name.clone(),
+                _ => String::new(),
+            },
+            locks: locks
                 .iter()
                 .map(|(key, value)| list_response::Lock {
                     name: key.to_owned(),
                     state: value.clone().locked(),
+                    holder: value.holder().unwrap_or_default(),
+                    expires_at: millis_from_time(value.expires_at()),
+                    token: value.token(),
+                    mode: match value.shared() {
+                        true => Mode::Shared.into(),
+                        false => Mode::Exclusive.into(),
+                    },
+                    holders: value.holders(),
+                    permits: value.permits(),
+                    used: value.used(),
+                    waiters: value.waiters(),
+                    reentrant: value.is_reentrant(),
+                    holds: value.holds(),
+                    metadata: Some(value.metadata().into()),
+                    subtree: value.subtree(),
                 })
                 .collect(),
         }))
@@ -100,99 +646,671 @@ where
         &self,
         request: Request<LockRequest>,
     ) -> Result<Response<LockResponse>, Status> {
-        let lock_name = request.into_inner().name;
-        self.handler.removed(lock_name.clone());
-        let msg = SwarmMessage {
-            payload: Payload::LockMessage(LockMessage {
-                name: lock_name,
-                action: Action::Removed.into(),
-                message_id: nano_id::base64::<21>(),
-            })
-            .into(),
+        self.leading()?;
+        let request = request.into_inner();
+        let message = LockMessage {
+            name: request.name.clone(),
+            action: Action::Removed.into(),
+            subtree: request.subtree,
+            message_id: nano_id::base64::<21>(),
+            ..Default::default()
         };
-        let mut buffer = vec![];
-        msg.encode(&mut buffer).unwrap();
-        self.swarm.lock().unwrap().message(buffer).unwrap();
+        if self.raft.is_some() {
+            self.propose(Payload::LockMessage(message)).await?;
+            return Ok(Response::new(LockResponse::default()));
+        }
+        match request.subtree {
+            true => self.handler.removed_all(request.name).map(|_| ())?,
+            false => self.handler.removed(request.name)?,
+        }
+        self.broadcast(message)?;
 
         Ok(Response::new(LockResponse::default()))
     }
     async fn lock(&self, request: Request<LockRequest>) -> Result<Response<LockResponse>, Status> {
-        let lock_name = request.into_inner().name;
-        self.handler.locked(lock_name.clone());
-        let msg = SwarmMessage {
-            payload: Payload::LockMessage(LockMessage {
-                name: lock_name,
-                action: Action::Locked.into(),
-                message_id: nano_id::base64::<21>(),
-            })
-            .into(),
-        };
-        let mut buffer = vec![];
-        msg.encode(&mut buffer).unwrap();
-        self.swarm.lock().unwrap().message(buffer).unwrap();
-
-        Ok(Response::new(LockResponse::default()))
+        self.leading()?;
+        let request = request.into_inner();
+        match self.try_lock(request.clone()).await? {
+            Some(token) => Ok(Response::new(LockResponse {
+                body: Some(lock_response::Body::Token(token)),
+                ..Default::default()
+            })),
+            None => Err(Error::AlreadyLocked.into()),
+        }
+    }
+    async fn acquire(
+        &self,
+        request: Request<LockRequest>,
+    ) -> Result<Response<LockResponse>, Status> {
+        self.leading()?;
+        let request = request.into_inner();
+        let deadline = acquire_deadline(request.timeout);
+        // Subscribe before the first attempt so no release in between is missed.
+        let mut events = self.handler.watch(100)?;
+        let mut waiting = None;
+        let mut renew_at = tokio::time::Instant::now();
+        loop {
+            if let Some(token) = self.try_lock(request.clone()).await? {
+                return Ok(Response::new(LockResponse {
+                    body: Some(lock_response::Body::Token(token)),
+                    ..Default::default()
+                }));
+            }
+            let now = tokio::time::Instant::now();
+            if now >= renew_at {
+                self.enqueue(&request.name, &request.holder, deadline)
+                    .await?;
+                renew_at = now + QUEUE_LEASE / 3;
+            }
+            if waiting.is_none() {
+                waiting = Some(Waiting {
+                    locker: self,
+                    name: request.name.clone(),
+                    holder: request.holder.clone(),
+                });
+            }
+            let released = released(
+                &mut events,
+                std::slice::from_ref(&request.name),
+                &request.holder,
+            );
+            let wake = deadline.map_or(renew_at, |deadline| deadline.min(renew_at));
+            match before(Some(wake), released).await {
+                Ok(Ok(true)) => continue,
+                Ok(Err(err)) => return Err(err.into()),
+                // Dropped for falling behind, the next attempt sees whatever
+                // it missed.
+                Ok(Ok(false)) => events = self.handler.watch(100)?,
+                Err(_) if matches!(deadline, Some(deadline) if deadline <= wake) => {
+                    return Err(Status::new(
+                        tonic::Code::DeadlineExceeded,
+                        format!("Timed out waiting for lock {}", request.name),
+                    ))
+                }
+                // Time to renew the place in the queue.
+                Err(_) => {}
+            }
+        }
+    }
+    async fn acquire_many(
+        &self,
+        request: Request<LockManyRequest>,
+    ) -> Result<Response<LockManyResponse>, Status> {
+        self.leading()?;
+        let request = request.into_inner();
+        let deadline = acquire_deadline(request.timeout);
+        let mut events = self.handler.watch(100)?;
+        loop {
+            if let Some(tokens) = self.try_lock_many(request.clone()).await? {
+                return Ok(Response::new(LockManyResponse { tokens }));
+            }
+            let released = released(&mut events, &request.names, &request.holder);
+            match before(deadline, released).await {
+                Ok(Ok(true)) => continue,
+                Ok(Err(err)) => return Err(err.into()),
+                // Dropped for falling behind, the next attempt sees whatever
+                // it missed.
+                Ok(Ok(false)) => events = self.handler.watch(100)?,
+                Err(_) => {
+                    return Err(Status::new(
+                        tonic::Code::DeadlineExceeded,
+                        format!("Timed out waiting for locks {:?}", request.names),
+                    ))
+                }
+            }
+        }
     }
     async fn unlock(
         &self,
         request: Request<LockRequest>,
     ) -> Result<Response<LockResponse>, Status> {
-        let lock_name = request.into_inner().name;
-        self.handler.unlocked(lock_name.clone());
-        let msg = SwarmMessage {
-            payload: Payload::LockMessage(LockMessage {
-                name: lock_name,
-                action: Action::Unlocked.into(),
-                message_id: nano_id::base64::<21>(),
-            })
-            .into(),
+        self.leading()?;
+        let request = request.into_inner();
+        require_holder(&request.holder)?;
+        if request.force && !self.allow_force {
+            return Err(Error::PermissionDenied(String::from(
+                "Forced unlocks are disabled, see api.allow_force",
+            ))
+            .into());
+        }
+        let message = LockMessage {
+            name: request.name.clone(),
+            action: Action::Unlocked.into(),
+            message_id: nano_id::base64::<21>(),
+            holder: request.holder.clone(),
+            force: request.force,
+            permits: request.permits,
+            ..Default::default()
         };
-        let mut buffer = vec![];
-        msg.encode(&mut buffer).unwrap();
-        self.swarm.lock().unwrap().message(buffer).unwrap();
+        if self.raft.is_some() {
+            self.propose(Payload::LockMessage(message)).await?;
+            return Ok(Response::new(LockResponse::default()));
+        }
+        if !request.force {
+            self.counting_holds(&request.name, &request.holder)?;
+        }
+        let holds =
+            self.handler
+                .unlocked(request.name, request.holder, request.permits, request.force)?;
+        if holds > 0 {
+            return Ok(Response::new(LockResponse::default()));
+        }
+        self.broadcast(message)?;
 
         Ok(Response::new(LockResponse::default()))
     }
-    async fn watch(&self, _: Request<Empty>) -> Result<Response<Self::WatchStream>, Status> {
-        let stream = self
+    async fn keep_alive(
+        &self,
+        request: Request<LockRequest>,
+    ) -> Result<Response<LockResponse>, Status> {
+        self.leading()?;
+        self.renew(request.into_inner()).await?;
+        Ok(Response::new(LockResponse::default()))
+    }
+    async fn keep_alive_stream(
+        &self,
+        request: Request<Streaming<LockRequest>>,
+    ) -> Result<Response<LockResponse>, Status> {
+        self.leading()?;
+        let mut stream = request.into_inner();
+        while let Some(request) = stream.message().await? {
+            self.renew(request).await?;
+        }
+        Ok(Response::new(LockResponse::default()))
+    }
+    async fn campaign(
+        &self,
+        request: Request<ElectionRequest>,
+    ) -> Result<Response<LeaderResponse>, Status> {
+        self.leading()?;
+        let request = request.into_inner();
+        if request.ttl == 0 {
+            return Err(Error::InvalidArgument(String::from("Campaign requires a ttl")).into());
+        }
+        let message = LockMessage {
+            name: request.name.clone(),
+            action: Action::Created.into(),
+            message_id: nano_id::base64::<21>(),
+            ..Default::default()
+        };
+        let created = match self.raft {
+            Some(_) => self
+                .propose(Payload::LockMessage(message))
+                .await
+                .map(|_| ()),
+            None => self
+                .handler
+                .created(request.name.clone(), 0, false)
+                .and_then(|_| self.broadcast(message)),
+        };
+        match created {
+            Ok(()) | Err(Error::AlreadyExists(_)) => {}
+            Err(err) => return Err(err.into()),
+        }
+        let acquired = self
+            .acquire(Request::new(LockRequest {
+                name: request.name.clone(),
+                holder: request.candidate.clone(),
+                ttl: request.ttl,
+                timeout: request.timeout,
+                ..Default::default()
+            }))
+            .await?
+            .into_inner();
+        Ok(Response::new(LeaderResponse {
+            name: request.name,
+            leader: request.candidate,
+            term: match acquired.body {
+                Some(lock_response::Body::Token(token)) => token,
+                _ => 0,
+            },
+        }))
+    }
+    async fn resign(
+        &self,
+        request: Request<ElectionRequest>,
+    ) -> Result<Response<LeaderResponse>, Status> {
+        self.leading()?;
+        let request = request.into_inner();
+        self.unlock(Request::new(LockRequest {
+            name: request.name.clone(),
+            holder: request.candidate,
+            ..Default::default()
+        }))
+        .await?;
+        Ok(Response::new(leader(&self.handler, request.name)?))
+    }
+    async fn leader(
+        &self,
+        request: Request<ElectionRequest>,
+    ) -> Result<Response<LeaderResponse>, Status> {
+        Ok(Response::new(leader(
+            &self.handler,
+            request.into_inner().name,
+        )?))
+    }
+    async fn observe(
+        &self,
+        request: Request<ElectionRequest>,
+    ) -> Result<Response<Self::ObserveStream>, Status> {
+        let name = request.into_inner().name;
+        let election = name.clone();
+        let handler = self.handler.clone();
+        let changes = self
             .handler
-            .watch(100)
-            .map_err(|err| Status::new(tonic::Code::Internal, err.to_string()))?
-            .map(|event| {
-                Ok(match event {
-                    event::Event::Unlocked(name) => LockEvent {
-                        name,
-                        status: lock_event::Status::Unlocked.into(),
-                    },
-                    event::Event::Locked(name) => LockEvent {
-                        name,
-                        status: lock_event::Status::Locked.into(),
-                    },
-                    event::Event::Created(name) => LockEvent {
-                        name,
-                        status: lock_event::Status::Created.into(),
-                    },
-                    event::Event::Removed(name) => LockEvent {
-                        name,
-                        status: lock_event::Status::Removed.into(),
-                    },
-                })
+            .watch(100)?
+            .filter(move |stamped| match &stamped.event {
+                event::Event::Locked(name)
+                | event::Event::Unlocked(name)
+                | event::Event::Expired(name)
+                | event::Event::Removed(name) => *name == election,
+                _ => false,
             });
+        let stream = tokio_stream::once(())
+            .chain(changes.map(|_| ()))
+            .map(move |_| leader(&handler, name.clone()))
+            .map_err(Status::from);
+        Ok(Response::new(Box::pin(stream)))
+    }
+    async fn create_barrier(
+        &self,
+        request: Request<BarrierRequest>,
+    ) -> Result<Response<BarrierResponse>, Status> {
+        self.leading()?;
+        let request = request.into_inner();
+        let message = Payload::BarrierMessage(BarrierMessage {
+            name: request.name.clone(),
+            action: barrier_message::Action::Created.into(),
+            message_id: nano_id::base64::<21>(),
+            parties: request.parties,
+            ..Default::default()
+        });
+        match self.raft {
+            Some(_) => self.propose(message).await.map(|_| ())?,
+            None => {
+                self.handler
+                    .barrier_created(request.name.clone(), request.parties)?;
+                self.gossip(message)?;
+            }
+        }
+        Ok(Response::new(self.barrier(request.name)?))
+    }
+    async fn enter(
+        &self,
+        request: Request<BarrierRequest>,
+    ) -> Result<Response<BarrierResponse>, Status> {
+        self.leading()?;
+        let request = request.into_inner();
+        if request.participant.is_empty() {
+            return Err(
+                Error::InvalidArgument(String::from("Enter requires a participant")).into(),
+            );
+        }
+        let deadline = acquire_deadline(request.timeout);
+        let mut events = self.handler.watch(100)?;
+        let message = BarrierMessage {
+            name: request.name.clone(),
+            action: barrier_message::Action::Entered.into(),
+            message_id: nano_id::base64::<21>(),
+            participant: request.participant.clone(),
+            ..Default::default()
+        };
+        let (generation, tripped) = match self.raft {
+            Some(_) => match self.propose(Payload::BarrierMessage(message)).await? {
+                Applied::Entered(generation, tripped) => (generation, tripped),
+                applied => return Err(unexpected(applied).into()),
+            },
+            None => {
+                let (generation, tripped) = self.handler.entered(
+                    request.name.clone(),
+                    request.participant.clone(),
+                    None,
+                )?;
+                self.gossip(Payload::BarrierMessage(BarrierMessage {
+                    generation,
+                    ..message
+                }))?;
+                (generation, tripped)
+            }
+        };
+        if tripped {
+            return Ok(Response::new(self.barrier(request.name)?));
+        }
+        let _arrival = Arrival {
+            locker: self,
+            name: request.name.clone(),
+            participant: request.participant.clone(),
+            generation,
+        };
+        loop {
+            let tripped = until(
+                &mut events,
+                |event| matches!(event, event::Event::Tripped(name) if *name == request.name),
+            );
+            match before(deadline, tripped).await {
+                Ok(true) => {}
+                // Dropped for falling behind, the barrier tells if it missed
+                // the trip.
+                Ok(false) => events = self.handler.watch(100)?,
+                Err(_) => {
+                    return Err(Status::new(
+                        tonic::Code::DeadlineExceeded,
+                        format!("Timed out waiting at barrier {}", request.name),
+                    ))
+                }
+            }
+            let barrier = self.barrier(request.name.clone())?;
+            if barrier.generation > generation {
+                return Ok(Response::new(barrier));
+            }
+        }
+    }
+    async fn create_latch(
+        &self,
+        request: Request<LatchRequest>,
+    ) -> Result<Response<LatchResponse>, Status> {
+        self.leading()?;
+        let request = request.into_inner();
+        let message = Payload::LatchMessage(LatchMessage {
+            name: request.name.clone(),
+            action: latch_message::Action::Created.into(),
+            message_id: nano_id::base64::<21>(),
+            count: request.count,
+        });
+        match self.raft {
+            Some(_) => self.propose(message).await.map(|_| ())?,
+            None => {
+                self.handler
+                    .latch_created(request.name.clone(), request.count)?;
+                self.gossip(message)?;
+            }
+        }
+        Ok(Response::new(LatchResponse {
+            name: request.name,
+            count: request.count,
+        }))
+    }
+    async fn count_down(
+        &self,
+        request: Request<LatchRequest>,
+    ) -> Result<Response<LatchResponse>, Status> {
+        self.leading()?;
+        let request = request.into_inner();
+        let amount = request.count.max(1);
+        let message = Payload::LatchMessage(LatchMessage {
+            name: request.name.clone(),
+            action: latch_message::Action::CountedDown.into(),
+            message_id: nano_id::base64::<21>(),
+            count: amount,
+        });
+        let count = match self.raft {
+            Some(_) => match self.propose(message).await? {
+                Applied::Count(count) => count,
+                applied => return Err(unexpected(applied).into()),
+            },
+            None => {
+                let count = self.handler.counted_down(request.name.clone(), amount)?;
+                self.gossip(message)?;
+                count
+            }
+        };
+        Ok(Response::new(LatchResponse {
+            name: request.name,
+            count,
+        }))
+    }
+    async fn wait(
+        &self,
+        request: Request<LatchRequest>,
+    ) -> Result<Response<LatchResponse>, Status> {
+        let request = request.into_inner();
+        let deadline = acquire_deadline(request.timeout);
+        let mut events = self.handler.watch(100)?;
+        while !self.handler.latch(request.name.clone())?.open() {
+            let opened = until(
+                &mut events,
+                |event| matches!(event, event::Event::Opened(name) if *name == request.name),
+            );
+            match before(deadline, opened).await {
+                Ok(true) => {}
+                // Dropped for falling behind, the latch tells if it missed
+                // the opening.
+                Ok(false) => events = self.handler.watch(100)?,
+                Err(_) => {
+                    return Err(Status::new(
+                        tonic::Code::DeadlineExceeded,
+                        format!("Timed out waiting for latch {}", request.name),
+                    ))
+                }
+            }
+        }
+        Ok(Response::new(LatchResponse {
+            name: request.name,
+            count: 0,
+        }))
+    }
+    async fn watch(
+        &self,
+        request: Request<WatchRequest>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        let request = request.into_inner();
+        let filter = event::Filter {
+            statuses: request.statuses().collect(),
+            name: request.name,
+            prefix: request.prefix,
+        };
+        let stream = self
+            .handler
+            .watch_filtered(100, filter, request.start_revision)?
+            .map(LockEvent::from)
+            .map(Ok);
         Ok(Response::new(Box::pin(stream)))
     }
 }
 
+/// Waits for an event `wanted` picks, `false` if the stream ended.
+async fn until(
+    events: &mut ReceiverStream<event::Stamped>,
+    wanted: impl Fn(&event::Event) -> bool,
+) -> bool {
+    while let Some(stamped) = events.next().await {
+        if wanted(&stamped.event) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Current leader of the election held in lock `name`, nobody leads an
+/// election no one campaigned for yet.
+fn leader<S: Storage<String, Lock> + Clone>(
+    handler: &Handler<S>,
+    name: String,
+) -> Result<LeaderResponse, Error> {
+    let lock = match handler.get(name.clone()) {
+        Ok(lock) => lock,
+        Err(Error::NotFound(_)) => Lock::new(),
+        Err(err) => return Err(err),
+    };
+    Ok(match lock.holder() {
+        Some(leader) => LeaderResponse {
+            name,
+            leader,
+            term: lock.token(),
+        },
+        None => LeaderResponse {
+            name,
+            ..Default::default()
+        },
+    })
+}
+
+/// Fails for an empty holder, a lock held by nobody in particular could be
+/// released by anyone.
+fn require_holder(holder: &str) -> Result<(), Error> {
+    match holder.is_empty() {
+        true => Err(Error::InvalidArgument(String::from("A holder is required"))),
+        false => Ok(()),
+    }
+}
+
+/// Counts the `acks` of the acquisition gossiped as `message_id` until
+/// `quorum` of the `peers` accepted it, returning false as soon as one
+/// refuses it. Acks carry no id of their own, so only the first answer of
+/// each peer counts, and none of nodes that are not peers.
+fn tally(
+    message_id: &str,
+    acks: &mpsc::Receiver<Ack>,
+    peers: &HashSet<String>,
+    quorum: usize,
+    timeout: Duration,
+) -> Result<bool, Error> {
+    let deadline = Instant::now() + timeout;
+    let mut answered = HashSet::new();
+    let mut accepted = 0;
+    loop {
+        if accepted >= quorum {
+            return Ok(true);
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        match acks.recv_timeout(left) {
+            Ok(ack) if !peers.contains(&ack.node) => {
+                debug!(
+                    "Dropping ack of {} from {}, not a peer",
+                    message_id, ack.node
+                );
+            }
+            Ok(ack) if !answered.insert(ack.node.clone()) => {
+                debug!("Dropping repeated ack of {} from {}", message_id, ack.node);
+            }
+            Ok(ack) if ack.accepted => accepted += 1,
+            Ok(ack) => {
+                debug!("{} refused {}: {}", ack.node, message_id, ack.reason);
+                return Ok(false);
+            }
+            Err(_) => {
+                return Err(Error::Swarm(format!(
+                    "{} of {} peers acknowledged the lock within {:?}",
+                    accepted, quorum, timeout
+                )))
+            }
+        }
+    }
+}
+
+/// A committed entry that came to something its kind never does.
+fn unexpected(applied: Applied) -> Error {
+    Error::Internal(format!("Unexpected outcome {:?}", applied))
+}
+
+/// When a blocking call with the given `timeout` in milliseconds gives up,
+/// `None` waits until the caller hangs up.
+fn acquire_deadline(timeout: u64) -> Option<tokio::time::Instant> {
+    match timeout {
+        0 => None,
+        timeout => Some(tokio::time::Instant::now() + Duration::from_millis(timeout)),
+    }
+}
+
+/// Waits for `future` until `deadline`, or for as long as it takes without
+/// one.
+async fn before<F: Future>(
+    deadline: Option<tokio::time::Instant>,
+    future: F,
+) -> Result<F::Output, tokio::time::error::Elapsed> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, future).await,
+        None => Ok(future.await),
+    }
+}
+
+/// Waits until one of `names` may have become free, `false` if the stream ended.
+/// Fails if `holder` got picked to break a deadlock while waiting.
+async fn released(
+    events: &mut ReceiverStream<event::Stamped>,
+    names: &[String],
+    holder: &str,
+) -> Result<bool, Error> {
+    while let Some(stamped) = events.next().await {
+        match stamped.event {
+            event::Event::Deadlock(deadlock)
+                if deadlock.holder == holder && names.contains(&deadlock.name) =>
+            {
+                return Err(Error::Deadlock(deadlock.description))
+            }
+            // Locks above or below the requested ones may be what blocked them.
+            event::Event::Unlocked(name)
+            | event::Event::Expired(name)
+            | event::Event::Removed(name)
+            | event::Event::Dequeued(name)
+                if names.iter().any(|other| namespace::related(other, &name)) =>
+            {
+                return Ok(true)
+            }
+            _ => {}
+        }
+    }
+    Ok(false)
+}
+
+/// Cuts requests off after `timeout`, except those to the `UNLIMITED` routes.
+#[derive(Clone)]
+struct Deadline<S> {
+    inner: S,
+    timeout: Duration,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for Deadline<S>
+where
+    S: tower::Service<http::Request<B>>,
+    S::Error: Into<tower::BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = tower::BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, tower::BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let unlimited = UNLIMITED.contains(&request.uri().path());
+        let response = self.inner.call(request);
+        let timeout = self.timeout;
+        Box::pin(async move {
+            match unlimited {
+                true => response.await.map_err(Into::into),
+                false => tokio::time::timeout(timeout, response)
+                    .await?
+                    .map_err(Into::into),
+            }
+        })
+    }
+}
+
 pub async fn serve<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
     addr: std::net::SocketAddr,
     handler: Handler<S>,
     swarm: Arc<Mutex<Swarm<Handler<S>>>>,
+    raft: Option<Raft<S>>,
+    quorum: usize,
+    peers: Vec<String>,
+    allow_force: bool,
 ) -> Result<(), anyhow::Error> {
     let locker = LockingServer::new(Locker {
-        handler: handler,
-        swarm: swarm,
+        handler,
+        swarm,
+        raft,
+        quorum,
+        peers: peers.into_iter().collect(),
+        allow_force,
     });
     let layer = tower::ServiceBuilder::new()
-        .timeout(Duration::from_secs(30))
+        .layer(tower::layer::layer_fn(|inner| Deadline {
+            inner,
+            timeout: REQUEST_TIMEOUT,
+        }))
         .layer(TraceLayer::new_for_http())
         .into_inner();
     Server::builder()
@@ -203,3 +1321,65 @@ pub async fn serve<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
         .await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::{Service, ServiceExt};
+
+    async fn call(path: &str) -> Result<(), tower::BoxError> {
+        let slow = tower::service_fn(|_: http::Request<()>| async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok::<(), std::convert::Infallible>(())
+        });
+        let mut deadline = Deadline {
+            inner: slow,
+            timeout: Duration::from_millis(20),
+        };
+        let request = http::Request::builder().uri(path).body(()).unwrap();
+        deadline.ready().await?.call(request).await
+    }
+
+    #[tokio::test]
+    async fn streams_outlive_the_request_timeout() {
+        call("/api.grpc.Locking/KeepAliveStream").await.unwrap();
+        call("/api.grpc.Locking/Watch").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn other_requests_are_cut_off() {
+        assert!(call("/api.grpc.Locking/Lock").await.is_err());
+    }
+
+    /// Tallies `acks` from the given nodes against a quorum of two out of
+    /// the peers p1, p2 and p3.
+    fn quorum(acks: &[(&str, bool)]) -> Result<bool, Error> {
+        let (tx, rx) = mpsc::channel();
+        for (node, accepted) in acks {
+            tx.send(Ack {
+                node: node.to_string(),
+                accepted: *accepted,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        let peers = ["p1", "p2", "p3"]
+            .iter()
+            .map(|peer| peer.to_string())
+            .collect();
+        tally("id", &rx, &peers, 2, Duration::from_millis(20))
+    }
+
+    #[test]
+    fn grants_once_a_quorum_accepted() {
+        assert!(matches!(quorum(&[("p1", true), ("p3", true)]), Ok(true)));
+        assert!(matches!(quorum(&[("p1", true), ("p2", false)]), Ok(false)));
+    }
+
+    #[test]
+    fn misses_the_quorum_with_too_few_acks() {
+        assert!(quorum(&[("p1", true)]).is_err());
+        assert!(quorum(&[("p1", true), ("p1", true)]).is_err());
+        assert!(quorum(&[("p1", true), ("stranger", true)]).is_err());
+    }
+}
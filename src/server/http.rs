@@ -1,4 +1,7 @@
 use crate::config::KustodioConfiguration;
+use crate::handler::Handler;
+use crate::lock::lock::Lock;
+use crate::storage::traits::Storage;
 use http::StatusCode;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Response, Server};
@@ -12,13 +15,32 @@ const INDEX: &str = "index.html";
 #[folder = "ui/dist"]
 struct UIAssets;
 
-async fn handle(req: Request<Body>, config: Arc<String>) -> Result<Response<Body>, Infallible> {
+/// Counters in the prometheus text format.
+fn metrics<S: Storage<String, Lock> + Clone>(handler: &Handler<S>) -> String {
+    format!(
+        "# HELP kustodio_swarm_duplicates_total Swarm messages dropped as seen already.\n\
+         # TYPE kustodio_swarm_duplicates_total counter\n\
+         kustodio_swarm_duplicates_total {}\n",
+        handler.duplicates()
+    )
+}
+
+async fn handle<S: Storage<String, Lock> + Clone>(
+    req: Request<Body>,
+    config: Arc<String>,
+    handler: Handler<S>,
+) -> Result<Response<Body>, Infallible> {
     let builder = Response::builder();
     let req_path = req.uri().path();
     info!("Serving {}", req_path);
     let path = Path::new(match req_path {
         "/" => "/index.html",
-        "/metrics" => todo!(),
+        "/metrics" => {
+            return Ok(builder
+                .header("Content-Type", "text/plain; version=0.0.4")
+                .body(metrics(&handler).into())
+                .unwrap())
+        }
         "/config" => {
             return Ok(builder
                 .header("Content-Type", "application/json")
@@ -49,14 +71,23 @@ async fn handle(req: Request<Body>, config: Arc<String>) -> Result<Response<Body
     )
 }
 
-pub async fn serve(addr: SocketAddr, config: KustodioConfiguration) -> Result<(), anyhow::Error> {
+pub async fn serve<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
+    addr: SocketAddr,
+    config: KustodioConfiguration,
+    handler: Handler<S>,
+) -> Result<(), anyhow::Error> {
     for i in UIAssets::iter() {
         debug!("{}", i);
     }
     let context = Arc::new(serde_json::to_string(&config)?);
     let service = make_service_fn(move |_| {
         let context = context.clone();
-        async { Ok::<_, Infallible>(service_fn(move |req| handle(req, context.clone()))) }
+        let handler = handler.clone();
+        async {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                handle(req, context.clone(), handler.clone())
+            }))
+        }
     });
     Server::bind(&addr).serve(service).await?;
     Ok(())
@@ -1,2 +1,5 @@
+pub mod detector;
 pub mod grpc;
 pub mod http;
+pub mod reaper;
+pub mod syncer;
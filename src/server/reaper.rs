@@ -0,0 +1,95 @@
+use crate::error::Error;
+use crate::handler::Handler;
+use crate::lock::lock::Lock;
+use crate::proto::swarm::{lock_message::Action, swarm_message::Payload, LockMessage};
+use crate::storage::traits::Storage;
+use crate::swarm::Raft;
+use std::time::Duration;
+
+pub const REAP_INTERVAL: Duration = Duration::from_millis(500);
+/// How long a lease may be overdue before nodes other than the one that
+/// handed the lock out expire it themselves.
+pub const REAP_GRACE: Duration = Duration::from_secs(5);
+
+/// Periodically releases locks whose lease has run out and takes waiters out
+/// of the queue once they meant to give up.
+///
+/// Every node runs its own reaper, but only the node that handed out a lock
+/// expires it on time and gossips that to its peers. The others only step in
+/// once the lease is `REAP_GRACE` overdue, when that node seems to be gone.
+/// Every node knows when waiters give up, so each drops them on its own.
+/// In raft mode only the leader reaps, through the log.
+pub async fn serve<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
+    handler: Handler<S>,
+    raft: Option<Raft<S>>,
+    interval: Duration,
+) -> Result<(), anyhow::Error> {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        let reaped = match &raft {
+            Some(raft) if raft.leading().is_err() => continue,
+            Some(raft) => reap_committed(&handler, raft).await,
+            None => handler.reap(REAP_GRACE),
+        };
+        match reaped {
+            Ok(expired) => {
+                for name in expired {
+                    info!("Lease of lock {} expired", name);
+                }
+            }
+            Err(err) => warn!("Could not reap expired locks: {}", err),
+        }
+        let lapsed = match &raft {
+            Some(raft) => lapse_committed(&handler, raft).await,
+            None => handler.lapse(),
+        };
+        match lapsed {
+            Ok(lapsed) => {
+                for (name, holder) in lapsed {
+                    info!("{:?} gave up waiting for lock {}", holder, name);
+                }
+            }
+            Err(err) => warn!("Could not drop lapsed waiters: {}", err),
+        }
+    }
+}
+
+/// Proposes the expiry of every overdue lock, the leader handed them all out.
+async fn reap_committed<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
+    handler: &Handler<S>,
+    raft: &Raft<S>,
+) -> Result<Vec<String>, Error> {
+    let mut expired = vec![];
+    for (name, token) in handler.overdue(Duration::ZERO)? {
+        raft.propose(Payload::LockMessage(LockMessage {
+            name: name.clone(),
+            action: Action::Expired.into(),
+            message_id: nano_id::base64::<21>(),
+            token,
+            ..Default::default()
+        }))
+        .await?;
+        expired.push(name);
+    }
+    Ok(expired)
+}
+
+/// Proposes taking every lapsed waiter out of its queue.
+async fn lapse_committed<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
+    handler: &Handler<S>,
+    raft: &Raft<S>,
+) -> Result<Vec<(String, String)>, Error> {
+    let lapsed = handler.lapsed()?;
+    for (name, holder) in lapsed.iter() {
+        raft.propose(Payload::LockMessage(LockMessage {
+            name: name.clone(),
+            action: Action::Dequeued.into(),
+            message_id: nano_id::base64::<21>(),
+            holder: holder.clone(),
+            ..Default::default()
+        }))
+        .await?;
+    }
+    Ok(lapsed)
+}
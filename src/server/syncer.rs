@@ -0,0 +1,23 @@
+use crate::handler::Handler;
+use crate::lock::lock::Lock;
+use crate::storage::traits::Storage;
+use std::time::Duration;
+
+pub const SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically gossips a digest of the locks this node knows.
+///
+/// Peers pull the locks they miss or hold in a stale state from it, so a node
+/// that joined or restarted catches up within an interval or so.
+pub async fn serve<S: Storage<String, Lock> + Clone + Send + Sync + 'static>(
+    handler: Handler<S>,
+    interval: Duration,
+) -> Result<(), anyhow::Error> {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if let Err(err) = handler.digest() {
+            warn!("Could not gossip digest: {}", err);
+        }
+    }
+}
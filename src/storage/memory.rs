@@ -1,15 +1,18 @@
-use super::traits::{Storage, StorageError};
+use super::traits::Storage;
+use crate::error::Error;
 use bloomfilter::Bloom;
-use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::{btree_map::Entry, BTreeMap};
 use std::hash::Hash;
+use std::ops::Bound;
 use std::sync::Arc;
 use std::sync::RwLock;
 
 #[derive(Clone)]
 pub struct Memory<Key, Value> {
     bloom_filter: Arc<RwLock<Bloom<Key>>>,
-    hash_map: Arc<RwLock<HashMap<Key, Value>>>,
+    /// Ordered by key, so scans can page through it.
+    hash_map: Arc<RwLock<BTreeMap<Key, Value>>>,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -25,14 +28,14 @@ impl<Key, Value> Memory<Key, Value> {
                 config.bitmap_size,
                 config.items_count,
             ))),
-            hash_map: Arc::new(RwLock::new(HashMap::new())),
+            hash_map: Arc::new(RwLock::new(BTreeMap::new())),
         }
     }
 }
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 impl<Key, Value> Storage<Key, Value> for Memory<Key, Value>
 where
-    Key: Hash + Eq + Clone + Debug,
+    Key: Hash + Ord + Clone + Debug + Display,
     Value: Clone + Debug,
 {
     fn clone_safe(&self) -> Self {
@@ -48,42 +51,85 @@ where
         }
     }
 
-    fn set(&self, key: Key, value: Value) -> Result<Option<Value>, StorageError> {
+    fn set(&self, key: Key, value: Value) -> Result<Option<Value>, Error> {
         match self.get(key.clone()) {
             Err(_) => {
                 self.bloom_filter.write().unwrap().set(&key);
-                match self.hash_map.write().unwrap().try_insert(key, value) {
-                    Ok(_) => Ok(None),
-                    Err(err) => Err(anyhow::Error::msg(format!(
+                match self.hash_map.write().unwrap().entry(key) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(value);
+                        Ok(None)
+                    }
+                    Entry::Occupied(entry) => Err(Error::Storage(format!(
                         "Storage Occupied! ({})",
-                        err.to_string()
+                        entry.key()
                     ))),
                 }
             }
             Ok(old) => {
-                self.hash_map.write().unwrap().remove(&key);
-                match self.hash_map.write().unwrap().try_insert(key, value) {
-                    Ok(_) => Ok(Some(old)),
-                    Err(err) => Err(anyhow::Error::msg(format!(
-                        "Storage Occupied! ({})",
-                        err.to_string()
-                    ))),
-                }
+                self.hash_map.write().unwrap().insert(key, value);
+                Ok(Some(old))
             }
         }
     }
 
-    fn get(&self, key: Key) -> Result<Value, StorageError> {
+    fn insert(&self, key: Key, value: Value) -> Result<(), Error> {
+        self.bloom_filter.write().unwrap().set(&key);
+        match self.hash_map.write().unwrap().entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+            Entry::Occupied(entry) => Err(Error::AlreadyExists(entry.key().to_string())),
+        }
+    }
+
+    fn update<T, F>(&self, key: Key, change: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Value) -> Result<T, Error>,
+    {
+        let mut hash_map = self.hash_map.write().unwrap();
+        let value = hash_map
+            .get_mut(&key)
+            .ok_or_else(|| Error::NotFound(key.to_string()))?;
+        let mut changed = value.clone();
+        let result = change(&mut changed)?;
+        *value = changed;
+        Ok(result)
+    }
+
+    fn update_all<T, F>(&self, keys: &[Key], change: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut [Value]) -> Result<T, Error>,
+    {
+        let mut hash_map = self.hash_map.write().unwrap();
+        let mut values = keys
+            .iter()
+            .map(|key| {
+                hash_map
+                    .get(key)
+                    .cloned()
+                    .ok_or_else(|| Error::NotFound(key.to_string()))
+            })
+            .collect::<Result<Vec<Value>, Error>>()?;
+        let result = change(&mut values)?;
+        for (key, value) in keys.iter().zip(values) {
+            hash_map.insert(key.clone(), value);
+        }
+        Ok(result)
+    }
+
+    fn get(&self, key: Key) -> Result<Value, Error> {
         match self.probe(key.clone()) {
             true => match self.hash_map.read().unwrap().get(&key) {
                 Some(value) => Ok(value.clone()),
-                None => Err(anyhow::Error::msg("Key not found.")),
+                None => Err(Error::NotFound(key.to_string())),
             },
-            false => Err(anyhow::Error::msg("Key not found.")),
+            false => Err(Error::NotFound(key.to_string())),
         }
     }
 
-    fn remove(&self, key: Key) -> Result<Value, StorageError> {
+    fn remove(&self, key: Key) -> Result<Value, Error> {
         match self.get(key.clone()) {
             Ok(value_ref) => {
                 let value = value_ref.clone();
@@ -94,14 +140,14 @@ where
         }
     }
 
-    fn swap(&self, key: Key, value_reference: &mut Value) -> Result<(), StorageError> {
+    fn swap(&self, key: Key, value_reference: &mut Value) -> Result<(), Error> {
         if let Some(old) = self.set(key, value_reference.clone())? {
             *value_reference = old;
         }
         Ok(())
     }
 
-    fn list(&self) -> Result<Vec<(Key, Value)>, StorageError> {
+    fn list(&self) -> Result<Vec<(Key, Value)>, Error> {
         Ok(self
             .hash_map
             .read()
@@ -110,4 +156,22 @@ where
             .map(|(key, value)| (key.clone(), value.clone()))
             .collect())
     }
+
+    fn scan(
+        &self,
+        from: Bound<Key>,
+        visit: &mut dyn FnMut(&Key, &Value) -> bool,
+    ) -> Result<(), Error> {
+        for (key, value) in self
+            .hash_map
+            .read()
+            .unwrap()
+            .range((from, Bound::Unbounded))
+        {
+            if !visit(key, value) {
+                break;
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,11 +1,32 @@
-pub type StorageError = anyhow::Error;
+use crate::error::Error;
+use std::ops::Bound;
 
 pub trait Storage<Key, Value> {
     fn clone_safe(&self) -> Self;
     fn probe(&self, key: Key) -> bool;
-    fn set(&self, key: Key, value: Value) -> Result<Option<Value>, StorageError>;
-    fn get(&self, key: Key) -> Result<Value, StorageError>;
-    fn remove(&self, key: Key) -> Result<Value, StorageError>;
-    fn swap(&self, key: Key, value_reference: &mut Value) -> Result<(), StorageError>;
-    fn list(&self) -> Result<Vec<(Key, Value)>, StorageError>;
+    fn set(&self, key: Key, value: Value) -> Result<Option<Value>, Error>;
+    /// Stores `value` under `key`, fails if the key is taken already.
+    fn insert(&self, key: Key, value: Value) -> Result<(), Error>;
+    /// Changes the value under `key` through `change`, which nothing else
+    /// can interleave with. If `change` fails the value stays as it was.
+    /// `change` must not call back into the storage.
+    fn update<T, F>(&self, key: Key, change: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Value) -> Result<T, Error>;
+    /// Like `update` for several keys at once, either all of them change or
+    /// none does.
+    fn update_all<T, F>(&self, keys: &[Key], change: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut [Value]) -> Result<T, Error>;
+    fn get(&self, key: Key) -> Result<Value, Error>;
+    fn remove(&self, key: Key) -> Result<Value, Error>;
+    fn swap(&self, key: Key, value_reference: &mut Value) -> Result<(), Error>;
+    fn list(&self) -> Result<Vec<(Key, Value)>, Error>;
+    /// Visits entries in key order, beginning at `from`, until `visit`
+    /// returns false. `visit` must not call back into the storage.
+    fn scan(
+        &self,
+        from: Bound<Key>,
+        visit: &mut dyn FnMut(&Key, &Value) -> bool,
+    ) -> Result<(), Error>;
 }
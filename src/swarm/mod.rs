@@ -1,3 +1,5 @@
 pub mod message;
+pub mod raft;
 pub mod swarm;
-pub use swarm::Swarm;
+pub use raft::Raft;
+pub use swarm::{forward, Swarm};
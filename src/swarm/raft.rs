@@ -0,0 +1,1140 @@
+use crate::error::Error;
+use crate::handler::handler::Applied;
+use crate::handler::Handler;
+use crate::lock::lock::Lock;
+use crate::proto::raft::{
+    raft_client::RaftClient,
+    raft_server::{self, RaftServer},
+    AppendRequest, AppendResponse, Entry, Persisted, Record, SnapshotRequest, VoteRequest,
+    VoteResponse,
+};
+use crate::proto::swarm::{swarm_message::Payload, SwarmMessage};
+use crate::proto::Peer;
+use crate::storage::traits::Storage;
+use prost::Message;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::{oneshot, Notify};
+use tonic::transport::{Channel, Endpoint, Server};
+use tonic::{Request, Response, Status};
+
+/// How often the leader replicates and followers check on it.
+const TICK: Duration = Duration::from_millis(50);
+/// Followers run for leader after hearing nothing for this long, plus up to
+/// `ELECTION_JITTER_MILLIS` so they rarely run at once.
+const ELECTION_TIMEOUT: Duration = Duration::from_millis(300);
+const ELECTION_JITTER_MILLIS: u64 = 300;
+const RPC_TIMEOUT: Duration = Duration::from_millis(250);
+/// How long a proposal may take to reach a majority.
+const COMMIT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Entries sent per AppendEntries call.
+const MAX_ENTRIES: usize = 64;
+/// Applied entries the log keeps before they are compacted into a snapshot.
+const COMPACT_AFTER: u64 = 1000;
+
+/// Hands a proposal what applying its entry came to.
+type Waiter = oneshot::Sender<Result<Applied, Error>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    Follower,
+    Candidate,
+    Leader,
+}
+
+struct State {
+    role: Role,
+    term: u64,
+    voted_for: Option<String>,
+    /// Index and term of the last entry compacted into `snapshot`.
+    offset: u64,
+    offset_term: u64,
+    /// Encoded `Snapshot` of the state up to `offset`, see `Handler::dump`.
+    snapshot: Vec<u8>,
+    /// `log[0]` holds the entry at index `offset + 1`.
+    log: Vec<Entry>,
+    /// Term, vote or snapshot changed since they were written to disk.
+    dirty: bool,
+    /// First index of the entries appended since the log was written to disk.
+    appended: Option<u64>,
+    /// The log was compacted or replaced, its file is written anew.
+    rewritten: bool,
+    /// Last index on disk here, the leader only counts its own copy of an
+    /// entry towards a commit once it is.
+    written: u64,
+    /// Snapshot to reset the handler to before applying further entries.
+    restore: Option<Vec<u8>>,
+    commit_index: u64,
+    last_applied: u64,
+    /// API address of the current leader.
+    leader: Option<String>,
+    /// When a follower runs for leader unless it hears from one.
+    deadline: Instant,
+    votes: HashSet<String>,
+    next_index: HashMap<String, u64>,
+    match_index: HashMap<String, u64>,
+    /// Peers with an AppendEntries call under way.
+    sending: HashSet<String>,
+    /// When each peer last answered or called.
+    contacted: HashMap<String, Instant>,
+    /// Proposals waiting for their entry to be applied, by index.
+    waiting: HashMap<u64, Waiter>,
+}
+
+impl State {
+    /// Picks up where the node left off before it stopped, if it ran before.
+    fn load(path: &Path) -> Result<Self, Error> {
+        let persisted = match fs::read(path) {
+            Ok(bytes) => {
+                Persisted::decode(&bytes[..]).map_err(|err| Error::Internal(err.to_string()))?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Persisted::default(),
+            Err(err) => return Err(Error::Internal(err.to_string())),
+        };
+        let offset = persisted.snapshot_index;
+        let mut log = vec![];
+        match fs::read(path.with_extension("log")) {
+            Ok(bytes) => {
+                let mut records = &bytes[..];
+                // A record cut short by a crash ends the log, it was never
+                // acknowledged.
+                while let Ok(record) = Record::decode_length_delimited(&mut records) {
+                    if record.index <= offset {
+                        continue;
+                    }
+                    let kept = (record.index - offset - 1) as usize;
+                    if kept > log.len() {
+                        break;
+                    }
+                    log.truncate(kept);
+                    log.push(record.entry.unwrap_or_default());
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(Error::Internal(err.to_string())),
+        }
+        let restore = match persisted.snapshot.is_empty() {
+            true => None,
+            false => Some(persisted.snapshot.clone()),
+        };
+        Ok(State {
+            role: Role::Follower,
+            term: persisted.term,
+            voted_for: match persisted.voted_for.as_str() {
+                "" => None,
+                _ => Some(persisted.voted_for),
+            },
+            offset: persisted.snapshot_index,
+            offset_term: persisted.snapshot_term,
+            snapshot: persisted.snapshot,
+            written: offset + log.len() as u64,
+            log,
+            dirty: false,
+            appended: None,
+            rewritten: false,
+            restore,
+            commit_index: persisted.snapshot_index,
+            last_applied: 0,
+            leader: None,
+            deadline: Instant::now() + election_timeout(),
+            votes: HashSet::new(),
+            next_index: HashMap::new(),
+            match_index: HashMap::new(),
+            sending: HashSet::new(),
+            contacted: HashMap::new(),
+            waiting: HashMap::new(),
+        })
+    }
+
+    fn last_index(&self) -> u64 {
+        self.offset + self.log.len() as u64
+    }
+
+    /// Entry at `index`, if it is neither compacted nor missing.
+    fn entry(&self, index: u64) -> Option<&Entry> {
+        match index > self.offset {
+            true => self.log.get((index - self.offset - 1) as usize),
+            false => None,
+        }
+    }
+
+    fn term_at(&self, index: u64) -> u64 {
+        match index == self.offset {
+            true => self.offset_term,
+            false => self.entry(index).map_or(0, |entry| entry.term),
+        }
+    }
+
+    /// Appends `entry` to the log, it is written with the next `changes`.
+    fn push(&mut self, entry: Entry) {
+        let index = self.last_index() + 1;
+        self.appended = Some(self.appended.map_or(index, |first| first.min(index)));
+        self.log.push(entry);
+    }
+
+    /// Drops the entries after `index`.
+    fn truncate(&mut self, index: u64) {
+        self.log.truncate((index - self.offset) as usize);
+        self.written = self.written.min(index);
+    }
+
+    /// What changed since the last call and is to be written to disk, in
+    /// this order.
+    fn changes(&mut self) -> Vec<Change> {
+        let mut changes = vec![];
+        if self.dirty {
+            changes.push(Change::State(Persisted {
+                term: self.term,
+                voted_for: self.voted_for.clone().unwrap_or_default(),
+                snapshot_index: self.offset,
+                snapshot_term: self.offset_term,
+                snapshot: self.snapshot.clone(),
+            }));
+            self.dirty = false;
+        }
+        match self.appended.take() {
+            _ if self.rewritten => {
+                changes.push(Change::Log(self.offset, self.log.clone()));
+                self.rewritten = false;
+            }
+            Some(first) if first > self.offset && first <= self.last_index() => {
+                let entries = self.log[(first - self.offset - 1) as usize..].to_vec();
+                changes.push(Change::Entries(first, entries));
+            }
+            _ => {}
+        }
+        changes
+    }
+
+    fn not_leader(&self) -> Error {
+        Error::NotLeader(self.leader.clone().unwrap_or_default())
+    }
+
+    /// Steps down to follower, moving on to `term` if it is newer.
+    fn follow(&mut self, term: u64) {
+        if term > self.term {
+            self.term = term;
+            self.voted_for = None;
+            self.leader = None;
+            self.dirty = true;
+        }
+        self.role = Role::Follower;
+        self.votes.clear();
+        self.abandon();
+    }
+
+    /// Fails the pending proposals. Should their entries still commit, they
+    /// get applied like on any other node.
+    fn abandon(&mut self) {
+        let leader = self.leader.clone().unwrap_or_default();
+        for (_, waiter) in self.waiting.drain() {
+            let _ = waiter.send(Err(Error::NotLeader(leader.clone())));
+        }
+    }
+
+    /// Commits up to the newest entry of this term a majority of `size`
+    /// nodes has, returns whether the commit index moved.
+    fn advance(&mut self, size: usize) -> bool {
+        let committed = self.commit_index;
+        let mut index = self.last_index();
+        while index > self.commit_index && self.term_at(index) == self.term {
+            let copies = (self.written >= index) as usize
+                + self
+                    .match_index
+                    .values()
+                    .filter(|matched| **matched >= index)
+                    .count();
+            if copies * 2 > size {
+                self.commit_index = index;
+                break;
+            }
+            index -= 1;
+        }
+        self.commit_index > committed
+    }
+}
+
+/// Timeout of the next election, randomized per call.
+fn election_timeout() -> Duration {
+    let jitter = RandomState::new().build_hasher().finish() % ELECTION_JITTER_MILLIS;
+    ELECTION_TIMEOUT + Duration::from_millis(jitter)
+}
+
+/// A change to what a peer keeps on disk, see `State::changes`.
+enum Change {
+    /// Term, vote and snapshot.
+    State(Persisted),
+    /// Entries appended to the log from the index given on.
+    Entries(u64, Vec<Entry>),
+    /// The whole log, following the snapshot up to the index given.
+    Log(u64, Vec<Entry>),
+}
+
+/// Where a peer keeps its state across restarts. Term, vote and snapshot go
+/// to one file, which is replaced as a whole, and the entries to a log file
+/// next to it, which records are only appended to until the log is
+/// compacted.
+struct Disk {
+    path: PathBuf,
+    log: File,
+}
+
+impl Disk {
+    /// Opens the files at `path` and writes the log loaded from them anew,
+    /// without whatever a crash left half written.
+    fn open(path: PathBuf, offset: u64, log: impl IntoIterator<Item = Entry>) -> io::Result<Self> {
+        let log = Disk::rewrite(&path, offset, log)?;
+        Ok(Disk { path, log })
+    }
+
+    fn write(&mut self, change: Change) -> io::Result<()> {
+        match change {
+            Change::State(persisted) => replace(&self.path, &persisted.encode_to_vec()),
+            Change::Entries(first, entries) => {
+                self.log.write_all(&records(first, entries))?;
+                self.log.sync_data()
+            }
+            Change::Log(offset, entries) => {
+                self.log = Disk::rewrite(&self.path, offset, entries)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces the log file with `log`, the entries after `offset`, and
+    /// opens it to append to.
+    fn rewrite(path: &Path, offset: u64, log: impl IntoIterator<Item = Entry>) -> io::Result<File> {
+        let path = path.with_extension("log");
+        replace(&path, &records(offset + 1, log))?;
+        OpenOptions::new().append(true).open(path)
+    }
+}
+
+/// Writes `bytes` to `path` by replacing the file, so a crash leaves either
+/// the old or the new one.
+fn replace(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let mut file = File::create(&partial)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    fs::rename(&partial, path)?;
+    if let Some(dir) = path.parent() {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Log file records of `entries`, the first of them at index `first`.
+fn records(first: u64, entries: impl IntoIterator<Item = Entry>) -> Vec<u8> {
+    let mut buffer = vec![];
+    for (index, entry) in (first..).zip(entries) {
+        let record = Record {
+            index,
+            entry: Some(entry),
+        };
+        // Writing to a vector cannot run out of space.
+        let _ = record.encode_length_delimited(&mut buffer);
+    }
+    buffer
+}
+
+/// A peer in raft mode. Lock operations are proposed to the leader, appended
+/// to its log and only applied, on the leader like on every follower, once a
+/// majority of the peers has them.
+#[derive(Clone)]
+pub struct Raft<S: Storage<String, Lock> + Clone> {
+    address: String,
+    api_address: String,
+    peers: Vec<String>,
+    clients: Arc<HashMap<String, RaftClient<Channel>>>,
+    state: Arc<Mutex<State>>,
+    /// Taken before `state` is let go to write what changed, see `persist`.
+    disk: Arc<Mutex<Disk>>,
+    handler: Handler<S>,
+    /// Wakes the leader to replicate a new entry right away.
+    proposed: Arc<Notify>,
+}
+
+impl<S> Raft<S>
+where
+    S: Storage<String, Lock> + Clone + Send + Sync + 'static,
+{
+    /// Must be called from within the tokio runtime. Keeps its state in
+    /// `data_dir` and resumes from it.
+    pub fn new(
+        address: String,
+        api_address: String,
+        peers: Vec<String>,
+        handler: Handler<S>,
+        data_dir: &Path,
+    ) -> Result<Self, Error> {
+        fs::create_dir_all(data_dir).map_err(|err| {
+            Error::Config(format!("Could not create {}: {}", data_dir.display(), err))
+        })?;
+        let path = data_dir.join("raft");
+        let state = State::load(&path)?;
+        let disk = Disk::open(path, state.offset, state.log.iter().cloned())
+            .map_err(|err| Error::Internal(format!("Could not open raft state: {}", err)))?;
+        let mut clients = HashMap::new();
+        for peer in peers.iter() {
+            let endpoint = Endpoint::from_shared(format!("http://{}", peer))
+                .map_err(|err| Error::Config(err.to_string()))?
+                .connect_timeout(RPC_TIMEOUT)
+                .timeout(RPC_TIMEOUT);
+            clients.insert(peer.clone(), RaftClient::new(endpoint.connect_lazy()));
+        }
+        Ok(Raft {
+            address,
+            api_address,
+            peers,
+            clients: Arc::new(clients),
+            state: Arc::new(Mutex::new(state)),
+            disk: Arc::new(Mutex::new(disk)),
+            handler,
+            proposed: Arc::new(Notify::new()),
+        })
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, State>, Error> {
+        self.state
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))
+    }
+
+    /// Writes what changed of `state` to disk, letting go of it meanwhile so
+    /// nothing waits for the disk but the caller. Once the entries of the
+    /// leader are written they count towards a commit.
+    fn persist(&self, mut state: MutexGuard<'_, State>) -> Result<(), Error> {
+        let changes = state.changes();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let written = state.last_index();
+        // Taken while the state is still held, so changes reach the disk in
+        // the order they were made.
+        let mut disk = self
+            .disk
+            .lock()
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        drop(state);
+        for change in changes {
+            disk.write(change)
+                .map_err(|err| Error::Internal(format!("Could not save raft state: {}", err)))?;
+        }
+        drop(disk);
+        let mut state = self.state()?;
+        state.written = state.written.max(written).min(state.last_index());
+        if state.role == Role::Leader && state.advance(self.size()) {
+            self.proposed.notify_one();
+        }
+        Ok(())
+    }
+
+    /// Runs `call` where waiting for the disk does not hold up the runtime.
+    async fn blocking<T, F>(&self, call: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(Raft<S>) -> Result<T, Error> + Send + 'static,
+    {
+        let raft = self.clone();
+        tokio::task::spawn_blocking(move || call(raft))
+            .await
+            .map_err(|err| Error::Internal(err.to_string()))?
+    }
+
+    /// Nodes in the cluster, this one included.
+    fn size(&self) -> usize {
+        self.peers.len() + 1
+    }
+
+    /// Fails unless this node leads, changes are only made on the leader.
+    pub fn leading(&self) -> Result<(), Error> {
+        let state = self.state()?;
+        match state.role {
+            Role::Leader => Ok(()),
+            _ => Err(state.not_leader()),
+        }
+    }
+
+    /// Appends `payload` to the log, blocks until a majority of the peers has
+    /// it and this node applied it, and returns what applying it came to. An
+    /// entry that does not commit in time may still commit later.
+    pub async fn propose(&self, payload: Payload) -> Result<Applied, Error> {
+        let mut data = vec![];
+        SwarmMessage {
+            payload: payload.into(),
+        }
+        .encode(&mut data)
+        .map_err(|err| Error::Internal(err.to_string()))?;
+        let (tx, mut rx) = oneshot::channel();
+        let index = self.blocking(move |raft| raft.offer(data, tx)).await?;
+        if let Ok(Ok(result)) = tokio::time::timeout(COMMIT_TIMEOUT, &mut rx).await {
+            return result;
+        }
+        if self.state()?.waiting.remove(&index).is_none() {
+            // Taken up by `step` meanwhile, which is applying it right now.
+            if let Ok(result) = rx.await {
+                return result;
+            }
+        }
+        Err(Error::Swarm(format!(
+            "Entry {} was not committed within {:?}",
+            index, COMMIT_TIMEOUT
+        )))
+    }
+
+    /// Appends `data` to the log of the leader and returns its index,
+    /// `waiter` is told what applying it came to.
+    fn offer(&self, data: Vec<u8>, waiter: Waiter) -> Result<u64, Error> {
+        let mut state = self.state()?;
+        if state.role != Role::Leader {
+            return Err(state.not_leader());
+        }
+        let term = state.term;
+        state.push(Entry { term, data });
+        let index = state.last_index();
+        state.waiting.insert(index, waiter);
+        self.proposed.notify_one();
+        self.persist(state)?;
+        Ok(index)
+    }
+
+    pub fn peers(&self) -> Result<Vec<Peer>, Error> {
+        let state = self.state()?;
+        Ok(self
+            .peers
+            .iter()
+            .map(|peer| Peer {
+                cluster_address: peer.clone(),
+                api_address: String::new(),
+                status: match state.contacted.get(peer) {
+                    Some(at) if at.elapsed() < ELECTION_TIMEOUT * 2 => 1,
+                    Some(_) => 0,
+                    None => 2,
+                },
+            })
+            .collect())
+    }
+
+    /// Answers the other peers on `address` and keeps the log going.
+    pub async fn serve(self, address: SocketAddr) -> Result<(), anyhow::Error> {
+        tokio::spawn(self.clone().run());
+        Server::builder()
+            .add_service(RaftServer::new(self))
+            .serve(address)
+            .await?;
+        Ok(())
+    }
+
+    async fn run(self) {
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(TICK) => {}
+                _ = self.proposed.notified() => {}
+            }
+            if let Err(err) = self.blocking(|raft| raft.step()).await {
+                warn!("Raft: {}", err);
+            }
+        }
+    }
+
+    /// Replicates as leader or runs for it once the leader went quiet, then
+    /// applies what got committed meanwhile in log order and hands the
+    /// proposals their result. Only `run` calls this, so entries are never
+    /// applied twice or out of order.
+    fn step(&self) -> Result<(), Error> {
+        let mut state = self.state()?;
+        let ballot = match state.role {
+            Role::Leader => {
+                self.heartbeat(&mut state);
+                None
+            }
+            _ if Instant::now() >= state.deadline => self.campaign(&mut state),
+            _ => None,
+        };
+        let restore = state.restore.take();
+        if restore.is_some() {
+            state.last_applied = state.offset;
+        }
+        let committed: Vec<(Vec<u8>, Option<Waiter>)> = (state.last_applied + 1
+            ..=state.commit_index)
+            .filter_map(|index| {
+                let data = state.entry(index)?.data.clone();
+                Some((data, state.waiting.remove(&index)))
+            })
+            .collect();
+        state.last_applied = state.commit_index;
+        let persisted = self.persist(state);
+        // Nobody is asked for a vote before this node's own is on disk.
+        if let (Some(ballot), Ok(())) = (ballot, &persisted) {
+            self.canvass(ballot);
+        }
+        if let Some(snapshot) = restore {
+            if let Err(err) = self.handler.reset(&snapshot) {
+                warn!("Raft: could not restore the snapshot: {}", err);
+            }
+        }
+        for (data, waiter) in committed {
+            let applied = match data.is_empty() {
+                true => Ok(Applied::Done),
+                false => self.handler.commit(&data),
+            };
+            match waiter {
+                Some(waiter) => {
+                    let _ = waiter.send(applied);
+                }
+                None => {
+                    if let Err(err) = applied {
+                        debug!("Raft: committed entry failed: {}", err);
+                    }
+                }
+            }
+        }
+        persisted?;
+        self.compact()
+    }
+
+    /// Replaces the applied entries with a snapshot once there are enough
+    /// of them.
+    fn compact(&self) -> Result<(), Error> {
+        let applied = {
+            let state = self.state()?;
+            if state.last_applied < state.offset + COMPACT_AFTER {
+                return Ok(());
+            }
+            state.last_applied
+        };
+        // Only `step` applies entries, the handler is at `applied` still.
+        let snapshot = self.handler.dump()?;
+        let mut state = self.state()?;
+        if state.offset >= applied {
+            return Ok(());
+        }
+        debug!("Raft: compacting the log up to {}", applied);
+        state.offset_term = state.term_at(applied);
+        let compacted = (applied - state.offset) as usize;
+        state.log.drain(..compacted);
+        state.offset = applied;
+        state.snapshot = snapshot;
+        state.dirty = true;
+        state.rewritten = true;
+        self.persist(state)
+    }
+
+    /// Sends every peer the entries it is missing, or nothing to keep it
+    /// following. Peers missing compacted entries get the snapshot instead.
+    fn heartbeat(&self, state: &mut State) {
+        for peer in self.peers.iter() {
+            if !state.sending.insert(peer.clone()) {
+                continue;
+            }
+            let raft = self.clone();
+            let peer = peer.clone();
+            let mut client = self.clients[&peer].clone();
+            let next_index = state.next_index.get(&peer).copied().unwrap_or(1);
+            if next_index <= state.offset {
+                let request = SnapshotRequest {
+                    term: state.term,
+                    leader: self.address.clone(),
+                    leader_api: self.api_address.clone(),
+                    last_index: state.offset,
+                    last_term: state.offset_term,
+                    snapshot: state.snapshot.clone(),
+                };
+                tokio::spawn(async move {
+                    let term = request.term;
+                    let response = client.install_snapshot(request).await;
+                    tokio::task::spawn_blocking(move || raft.appended(&peer, term, response));
+                });
+                continue;
+            }
+            let prev_log_index = next_index - 1;
+            let request = AppendRequest {
+                term: state.term,
+                leader: self.address.clone(),
+                leader_api: self.api_address.clone(),
+                prev_log_index,
+                prev_log_term: state.term_at(prev_log_index),
+                entries: state.log[(prev_log_index - state.offset) as usize..]
+                    .iter()
+                    .take(MAX_ENTRIES)
+                    .cloned()
+                    .collect(),
+                leader_commit: state.commit_index,
+            };
+            tokio::spawn(async move {
+                let term = request.term;
+                let response = client.append_entries(request).await;
+                tokio::task::spawn_blocking(move || raft.appended(&peer, term, response));
+            });
+        }
+    }
+
+    fn appended(&self, peer: &str, term: u64, response: Result<Response<AppendResponse>, Status>) {
+        let mut state = match self.state() {
+            Ok(state) => state,
+            Err(_) => return,
+        };
+        state.sending.remove(peer);
+        let response = match response {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                debug!("Raft: {} did not answer: {}", peer, status.message());
+                return;
+            }
+        };
+        state.contacted.insert(peer.to_string(), Instant::now());
+        if response.term > state.term {
+            state.follow(response.term);
+            if let Err(err) = self.persist(state) {
+                warn!("Raft: {}", err);
+            }
+            return;
+        }
+        if state.role != Role::Leader || state.term != term {
+            return;
+        }
+        state
+            .next_index
+            .insert(peer.to_string(), response.match_index + 1);
+        if response.success {
+            state
+                .match_index
+                .insert(peer.to_string(), response.match_index);
+            if state.advance(self.size()) {
+                self.proposed.notify_one();
+            }
+        }
+    }
+
+    /// Runs for leader in the next term and returns the vote to ask the
+    /// peers for, none if this node leads right away.
+    fn campaign(&self, state: &mut State) -> Option<VoteRequest> {
+        state.term += 1;
+        state.role = Role::Candidate;
+        state.voted_for = Some(self.address.clone());
+        state.votes = HashSet::from([self.address.clone()]);
+        state.leader = None;
+        state.deadline = Instant::now() + election_timeout();
+        state.dirty = true;
+        info!("Raft: {} runs for term {}", self.address, state.term);
+        if state.votes.len() * 2 > self.size() {
+            self.lead(state);
+            return None;
+        }
+        Some(VoteRequest {
+            term: state.term,
+            candidate: self.address.clone(),
+            last_log_index: state.last_index(),
+            last_log_term: state.term_at(state.last_index()),
+        })
+    }
+
+    /// Asks every peer for its vote.
+    fn canvass(&self, request: VoteRequest) {
+        for peer in self.peers.iter() {
+            let raft = self.clone();
+            let peer = peer.clone();
+            let request = request.clone();
+            let mut client = self.clients[&peer].clone();
+            tokio::spawn(async move {
+                let term = request.term;
+                let response = client.request_vote(request).await;
+                tokio::task::spawn_blocking(move || raft.voted(&peer, term, response));
+            });
+        }
+    }
+
+    fn voted(&self, peer: &str, term: u64, response: Result<Response<VoteResponse>, Status>) {
+        let mut state = match self.state() {
+            Ok(state) => state,
+            Err(_) => return,
+        };
+        let response = match response {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                debug!("Raft: {} did not vote: {}", peer, status.message());
+                return;
+            }
+        };
+        state.contacted.insert(peer.to_string(), Instant::now());
+        if response.term > state.term {
+            state.follow(response.term);
+        } else if state.role == Role::Candidate && state.term == term && response.granted {
+            state.votes.insert(peer.to_string());
+            if state.votes.len() * 2 > self.size() {
+                self.lead(&mut state);
+            }
+        }
+        if let Err(err) = self.persist(state) {
+            warn!("Raft: {}", err);
+        }
+    }
+
+    /// Takes over as leader. The empty entry it starts the term with commits
+    /// whatever earlier leaders left uncommitted.
+    fn lead(&self, state: &mut State) {
+        info!("Raft: {} leads term {}", self.address, state.term);
+        state.role = Role::Leader;
+        state.leader = Some(self.api_address.clone());
+        let next_index = state.last_index() + 1;
+        for peer in self.peers.iter() {
+            state.next_index.insert(peer.clone(), next_index);
+            state.match_index.insert(peer.clone(), 0);
+        }
+        let term = state.term;
+        state.push(Entry { term, data: vec![] });
+        self.proposed.notify_one();
+    }
+
+    /// Follows the leader of `request` and appends its entries, dropping
+    /// those that conflict with them. Answers once they are on disk.
+    fn append(&self, request: AppendRequest) -> Result<AppendResponse, Error> {
+        let mut state = self.state()?;
+        if request.term < state.term {
+            return Ok(AppendResponse {
+                term: state.term,
+                success: false,
+                match_index: 0,
+            });
+        }
+        state.follow(request.term);
+        state.leader = Some(request.leader_api);
+        state.contacted.insert(request.leader, Instant::now());
+        state.deadline = Instant::now() + election_timeout();
+        let mut index = request.prev_log_index;
+        // Compacted entries were committed, so they match the leader's.
+        if index > state.last_index()
+            || (index >= state.offset && state.term_at(index) != request.prev_log_term)
+        {
+            let response = AppendResponse {
+                term: state.term,
+                success: false,
+                match_index: index.saturating_sub(1).min(state.last_index()),
+            };
+            self.persist(state)?;
+            return Ok(response);
+        }
+        for entry in request.entries {
+            index += 1;
+            if index <= state.offset {
+                continue;
+            }
+            match state.entry(index) {
+                Some(existing) if existing.term == entry.term => continue,
+                Some(_) => state.truncate(index - 1),
+                None => {}
+            }
+            state.push(entry);
+        }
+        if request.leader_commit > state.commit_index {
+            state.commit_index = request.leader_commit.min(index);
+        }
+        let response = AppendResponse {
+            term: state.term,
+            success: true,
+            match_index: index,
+        };
+        self.persist(state)?;
+        Ok(response)
+    }
+
+    /// Votes for a candidate at least as up to date as this node, once per
+    /// term.
+    fn vote(&self, request: VoteRequest) -> Result<VoteResponse, Error> {
+        let mut state = self.state()?;
+        if request.term > state.term {
+            state.follow(request.term);
+        }
+        let last_index = state.last_index();
+        let granted = request.term == state.term
+            && state
+                .voted_for
+                .as_ref()
+                .map_or(true, |voted_for| *voted_for == request.candidate)
+            && (request.last_log_term, request.last_log_index)
+                >= (state.term_at(last_index), last_index);
+        if granted {
+            state.voted_for = Some(request.candidate.clone());
+            state.deadline = Instant::now() + election_timeout();
+            state.dirty = true;
+        }
+        state.contacted.insert(request.candidate, Instant::now());
+        let response = VoteResponse {
+            term: state.term,
+            granted,
+        };
+        self.persist(state)?;
+        Ok(response)
+    }
+
+    /// Follows the leader of `request` and takes over its snapshot, keeping
+    /// the entries after it if they match the leader's.
+    fn install(&self, request: SnapshotRequest) -> Result<AppendResponse, Error> {
+        let mut state = self.state()?;
+        if request.term < state.term {
+            return Ok(AppendResponse {
+                term: state.term,
+                success: false,
+                match_index: 0,
+            });
+        }
+        state.follow(request.term);
+        state.leader = Some(request.leader_api);
+        state.contacted.insert(request.leader, Instant::now());
+        state.deadline = Instant::now() + election_timeout();
+        // Everything the snapshot covers is applied here already otherwise.
+        if request.last_index > state.commit_index {
+            info!("Raft: installing snapshot up to {}", request.last_index);
+            let log = match state.entry(request.last_index) {
+                Some(entry) if entry.term == request.last_term => {
+                    let compacted = (request.last_index - state.offset) as usize;
+                    state.log.split_off(compacted)
+                }
+                _ => vec![],
+            };
+            state.log = log;
+            state.offset = request.last_index;
+            state.written = state.written.min(state.last_index());
+            state.offset_term = request.last_term;
+            state.snapshot = request.snapshot.clone();
+            state.restore = Some(request.snapshot);
+            state.commit_index = request.last_index;
+            state.dirty = true;
+            state.rewritten = true;
+        }
+        let response = AppendResponse {
+            term: state.term,
+            success: true,
+            match_index: request.last_index,
+        };
+        self.persist(state)?;
+        Ok(response)
+    }
+}
+
+#[tonic::async_trait]
+impl<S> raft_server::Raft for Raft<S>
+where
+    S: Storage<String, Lock> + Clone + Send + Sync + 'static,
+{
+    async fn append_entries(
+        &self,
+        request: Request<AppendRequest>,
+    ) -> Result<Response<AppendResponse>, Status> {
+        let request = request.into_inner();
+        Ok(Response::new(
+            self.blocking(move |raft| raft.append(request)).await?,
+        ))
+    }
+
+    async fn request_vote(
+        &self,
+        request: Request<VoteRequest>,
+    ) -> Result<Response<VoteResponse>, Status> {
+        let request = request.into_inner();
+        Ok(Response::new(
+            self.blocking(move |raft| raft.vote(request)).await?,
+        ))
+    }
+
+    async fn install_snapshot(
+        &self,
+        request: Request<SnapshotRequest>,
+    ) -> Result<Response<AppendResponse>, Status> {
+        let request = request.into_inner();
+        Ok(Response::new(
+            self.blocking(move |raft| raft.install(request)).await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::memory::{Config, Memory};
+
+    fn data_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("kustodio-raft-{}-{}", name, std::process::id()))
+    }
+
+    fn raft(name: &str) -> Raft<Memory<String, Lock>> {
+        let _ = fs::remove_dir_all(data_dir(name));
+        reopen(name)
+    }
+
+    /// Picks up the state a raft of the same name left behind.
+    fn reopen(name: &str) -> Raft<Memory<String, Lock>> {
+        let storage = Memory::new(Config {
+            bitmap_size: 1024,
+            items_count: 64,
+        });
+        let (outbox, _) = tokio::sync::mpsc::unbounded_channel();
+        let handler = Handler::new(storage, String::from("node"), outbox);
+        Raft::new(
+            String::from("node"),
+            String::from("api"),
+            vec![],
+            handler,
+            &data_dir(name),
+        )
+        .unwrap()
+    }
+
+    fn entries(terms: &[u64]) -> Vec<Entry> {
+        terms
+            .iter()
+            .map(|term| Entry {
+                term: *term,
+                data: vec![],
+            })
+            .collect()
+    }
+
+    fn request(term: u64, prev: (u64, u64), terms: &[u64], leader_commit: u64) -> AppendRequest {
+        AppendRequest {
+            term,
+            leader: String::from("leader"),
+            leader_api: String::from("leader-api"),
+            prev_log_index: prev.0,
+            prev_log_term: prev.1,
+            entries: entries(terms),
+            leader_commit,
+        }
+    }
+
+    fn terms(raft: &Raft<Memory<String, Lock>>) -> Vec<u64> {
+        raft.state()
+            .unwrap()
+            .log
+            .iter()
+            .map(|entry| entry.term)
+            .collect()
+    }
+
+    #[test]
+    fn appends_entries_that_follow_the_log() {
+        let raft = raft("follow");
+        let response = raft.append(request(1, (0, 0), &[1, 1], 0)).unwrap();
+        assert!(response.success);
+        assert_eq!(response.match_index, 2);
+        let response = raft.append(request(1, (2, 1), &[1], 0)).unwrap();
+        assert!(response.success);
+        assert_eq!(response.match_index, 3);
+        assert_eq!(terms(&raft), vec![1, 1, 1]);
+        assert_eq!(raft.state().unwrap().leader.as_deref(), Some("leader-api"));
+    }
+
+    #[test]
+    fn rejects_entries_that_do_not_follow_the_log() {
+        let raft = raft("mismatch");
+        raft.append(request(1, (0, 0), &[1, 1], 0)).unwrap();
+        // Missing entries in between.
+        let response = raft.append(request(2, (4, 2), &[2], 0)).unwrap();
+        assert!(!response.success);
+        assert_eq!(response.match_index, 2);
+        // Previous entry from another term.
+        let response = raft.append(request(2, (2, 2), &[2], 0)).unwrap();
+        assert!(!response.success);
+        assert_eq!(response.match_index, 1);
+        assert_eq!(terms(&raft), vec![1, 1]);
+    }
+
+    #[test]
+    fn rejects_a_stale_leader() {
+        let raft = raft("stale");
+        raft.append(request(2, (0, 0), &[2], 0)).unwrap();
+        let response = raft.append(request(1, (1, 2), &[1], 0)).unwrap();
+        assert!(!response.success);
+        assert_eq!(response.term, 2);
+        assert_eq!(terms(&raft), vec![2]);
+    }
+
+    #[test]
+    fn drops_conflicting_entries() {
+        let raft = raft("conflict");
+        raft.append(request(1, (0, 0), &[1, 1, 1], 0)).unwrap();
+        let response = raft.append(request(2, (1, 1), &[2], 0)).unwrap();
+        assert!(response.success);
+        assert_eq!(terms(&raft), vec![1, 2]);
+    }
+
+    #[test]
+    fn keeps_entries_past_a_late_request() {
+        let raft = raft("late");
+        raft.append(request(1, (0, 0), &[1, 1, 1], 0)).unwrap();
+        let response = raft.append(request(1, (0, 0), &[1], 0)).unwrap();
+        assert!(response.success);
+        assert_eq!(response.match_index, 1);
+        assert_eq!(terms(&raft), vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn commits_no_further_than_the_entries_sent() {
+        let raft = raft("commit");
+        raft.append(request(1, (0, 0), &[1, 1, 1], 0)).unwrap();
+        raft.append(request(1, (0, 0), &[1], 3)).unwrap();
+        assert_eq!(raft.state().unwrap().commit_index, 1);
+        raft.append(request(1, (3, 1), &[], 3)).unwrap();
+        assert_eq!(raft.state().unwrap().commit_index, 3);
+    }
+
+    #[test]
+    fn advances_only_on_a_majority_in_the_current_term() {
+        let mut state = State::load(Path::new("/nonexistent/raft")).unwrap();
+        state.term = 2;
+        state.log = entries(&[1, 2]);
+        state.written = 2;
+        state.match_index.insert(String::from("a"), 1);
+        state.match_index.insert(String::from("b"), 0);
+        // Entry 1 is on a majority, but from an earlier term.
+        assert!(!state.advance(3));
+        assert_eq!(state.commit_index, 0);
+        state.match_index.insert(String::from("a"), 2);
+        assert!(state.advance(3));
+        assert_eq!(state.commit_index, 2);
+        state.log.extend(entries(&[2]));
+        assert!(!state.advance(5));
+    }
+
+    #[test]
+    fn counts_its_own_entries_once_written() {
+        let mut state = State::load(Path::new("/nonexistent/raft")).unwrap();
+        state.term = 1;
+        state.log = entries(&[1]);
+        state.match_index.insert(String::from("a"), 1);
+        assert!(!state.advance(3));
+        state.written = 1;
+        assert!(state.advance(3));
+    }
+
+    #[test]
+    fn resumes_from_the_appended_log() {
+        let raft = raft("resume");
+        raft.append(request(1, (0, 0), &[1, 1, 1], 0)).unwrap();
+        raft.append(request(2, (1, 1), &[2], 0)).unwrap();
+        raft.append(request(2, (2, 2), &[2, 2], 0)).unwrap();
+        assert_eq!(terms(&raft), vec![1, 2, 2, 2]);
+        drop(raft);
+        let raft = reopen("resume");
+        assert_eq!(terms(&raft), vec![1, 2, 2, 2]);
+        assert_eq!(raft.state().unwrap().term, 2);
+        // Only the entries appended since are written.
+        raft.append(request(2, (4, 2), &[2], 0)).unwrap();
+        let log = fs::read(data_dir("resume").join("raft.log")).unwrap();
+        let mut records = &log[..];
+        let mut indexes = vec![];
+        while let Ok(record) = Record::decode_length_delimited(&mut records) {
+            indexes.push(record.index);
+        }
+        assert_eq!(indexes, vec![1, 2, 3, 4, 5]);
+    }
+}
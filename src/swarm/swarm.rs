@@ -1,8 +1,10 @@
+use crate::error::Error;
 use gossip::{GossipService, Peer, UpdateHandler};
 
 use std::marker::Send;
 use std::sync::Arc;
 use std::sync::Mutex;
+use tokio::sync::mpsc::UnboundedReceiver;
 
 pub struct Swarm<Handler: UpdateHandler + 'static + Send> {
     existing_peers: Option<Vec<Peer>>,
@@ -39,24 +41,24 @@ where
             handler: handler,
         }
     }
-    pub fn start(&'a mut self) -> Result<(), anyhow::Error> {
+    pub fn start(&'a mut self) -> Result<(), Error> {
         let mut gs = self.gossip_service.lock().unwrap();
         let peers = self.existing_peers.clone();
         match gs.start(Box::new(move || peers), Box::new(self.handler.clone())) {
             Ok(_) => Ok(()),
-            Err(err) => Err(anyhow::Error::msg(err.to_string())),
+            Err(err) => Err(Error::Swarm(err.to_string())),
         }
     }
-    pub fn message(&mut self, message: Vec<u8>) -> Result<(), anyhow::Error> {
+    pub fn message(&mut self, message: Vec<u8>) -> Result<(), Error> {
         match self.gossip_service.lock().unwrap().submit(message) {
             Ok(_) => Ok(()),
-            Err(err) => Err(anyhow::Error::msg(err.to_string())),
+            Err(err) => Err(Error::Swarm(err.to_string())),
         }
     }
-    pub fn shutdown(&mut self) -> Result<(), anyhow::Error> {
+    pub fn shutdown(&mut self) -> Result<(), Error> {
         match self.gossip_service.lock().unwrap().shutdown() {
             Ok(n) => Ok(n),
-            Err(e) => Err(anyhow::Error::msg(e.to_string())),
+            Err(e) => Err(Error::Swarm(e.to_string())),
         }
     }
 
@@ -64,3 +66,19 @@ where
         self.gossip_service.lock().unwrap().peers().clone()
     }
 }
+
+/// Gossips the messages the handler queues up in `outbox`.
+pub async fn forward<Handler>(
+    mut outbox: UnboundedReceiver<Vec<u8>>,
+    swarm: Arc<Mutex<Swarm<Handler>>>,
+) -> Result<(), anyhow::Error>
+where
+    Handler: UpdateHandler + Send + Clone,
+{
+    while let Some(message) = outbox.recv().await {
+        if let Err(err) = swarm.lock().unwrap().message(message) {
+            warn!("Could not gossip: {}", err);
+        }
+    }
+    Ok(())
+}
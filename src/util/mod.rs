@@ -1,6 +1,7 @@
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 lazy_static! {
     static ref PEERS_AGE: Mutex<HashMap<u16, u16>> = Mutex::new(HashMap::new());
@@ -29,3 +30,62 @@ pub fn inc<'a>(n: &'a mut u16) -> u16 {
     *n = *n + 1;
     return r;
 }
+
+pub fn millis_from_time(time: Option<SystemTime>) -> u64 {
+    match time {
+        Some(time) => time
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default(),
+        None => 0,
+    }
+}
+
+pub fn time_from_millis(millis: u64) -> Option<SystemTime> {
+    match millis {
+        0 => None,
+        millis => Some(UNIX_EPOCH + Duration::from_millis(millis)),
+    }
+}
+
+pub fn deadline_from_ttl(ttl: u64) -> Option<SystemTime> {
+    match ttl {
+        0 => None,
+        ttl => Some(SystemTime::now() + Duration::from_millis(ttl)),
+    }
+}
+
+/// FNV-1a hash over a canonical encoding, integers little endian and
+/// strings prefixed with their length. Unlike `DefaultHasher` it comes out
+/// the same on every build and platform, so peers can compare the result.
+pub struct Fingerprint(u64);
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Fingerprint(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fingerprint {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        self.bytes(&value.to_le_bytes());
+        self
+    }
+
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.u64(value.len() as u64);
+        self.bytes(value.as_bytes());
+        self
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -9,6 +9,8 @@ use tracing::*;
 use web_sys::window;
 
 const GRPC_HEADER_SIZE: usize = 5;
+/// Holder of the locks taken through the UI.
+pub const HOLDER: &str = "kustodio-ui";
 
 #[derive(Clone, Debug)]
 pub struct Client {
@@ -93,6 +95,17 @@ impl Client {
             .body(Self::encode_body(msg))
             .send()
             .await?;
+        if let Some(status) = response.headers().get("grpc-status") {
+            if status != "0" {
+                let message = response
+                    .headers()
+                    .get("grpc-message")
+                    .and_then(|message| message.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                return Err(Error::msg(message));
+            }
+        }
         Ok(response.bytes().await?.to_vec())
     }
 
@@ -127,15 +140,16 @@ impl Client {
         resp.merge_from_bytes(&proto.to_vec())?;
         Ok(resp.get_peers().to_owned())
     }
-    pub async fn list(&self) -> Result<Vec<ListResponse_Lock>, Error> {
-        let req = Empty::new();
+    pub async fn list(&self, page_token: &str) -> Result<ListResponse, Error> {
+        let mut req = ListRequest::new();
+        req.set_page_token(page_token.into());
         let bytes = self
             .request("List", protobuf::Message::write_to_bytes(&req)?)
             .await?;
         let mut resp = ListResponse::new();
         let proto = Self::decode_body(bytes.clone()).await;
         resp.merge_from_bytes(&proto.to_vec())?;
-        Ok(resp.get_locks().to_owned())
+        Ok(resp)
     }
     pub async fn create(&self, name: &str) -> Result<LockResponse, Error> {
         let mut req = LockRequest::new();
@@ -151,6 +165,7 @@ impl Client {
     pub async fn lock(&self, name: &str) -> Result<LockResponse, Error> {
         let mut req = LockRequest::new();
         req.set_name(name.into());
+        req.set_holder(HOLDER.into());
         let bytes = self
             .request("Lock", protobuf::Message::write_to_bytes(&req)?)
             .await?;
@@ -159,9 +174,12 @@ impl Client {
         resp.merge_from_bytes(&proto.to_vec())?;
         Ok(resp)
     }
-    pub async fn unlock(&self, name: &str) -> Result<LockResponse, Error> {
+    /// Releases a lock the UI holds, or whoever holds it with `force`.
+    pub async fn unlock(&self, name: &str, force: bool) -> Result<LockResponse, Error> {
         let mut req = LockRequest::new();
         req.set_name(name.into());
+        req.set_holder(HOLDER.into());
+        req.set_force(force);
         let bytes = self
             .request("Unlock", protobuf::Message::write_to_bytes(&req)?)
             .await?;
@@ -1,5 +1,5 @@
 use crate::app::SwitchProps;
-use crate::client::Client;
+use crate::client::{Client, HOLDER};
 use crate::proto::*;
 use crate::utils::new_hero;
 use wasm_bindgen::JsCast;
@@ -7,17 +7,24 @@ use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
 pub enum Msg {
-    Init(Result<Vec<ListResponse_Lock>, String>),
+    Init(Result<ListResponse, String>),
     Update((String, bool)),
+    ForceUnlock(String),
     Remove(String),
     Reload,
     Create(String),
+    Next,
+    Previous,
 }
 
 pub struct Locks {
     client: Client,
     locks: Vec<ListResponse_Lock>,
     error: Option<anyhow::Error>,
+    /// Token of the shown page and of the pages before it.
+    page_token: String,
+    previous_pages: Vec<String>,
+    next_page_token: String,
 }
 
 impl Component for Locks {
@@ -30,6 +37,9 @@ impl Component for Locks {
             client,
             locks: vec![],
             error: None,
+            page_token: String::new(),
+            previous_pages: vec![],
+            next_page_token: String::new(),
         };
         locks.init(ctx);
         locks
@@ -39,7 +49,10 @@ impl Component for Locks {
         match msg {
             Msg::Init(resp) => {
                 match resp {
-                    Ok(locks) => self.locks = locks,
+                    Ok(page) => {
+                        self.locks = page.get_locks().to_owned();
+                        self.next_page_token = page.get_next_page_token().to_string();
+                    }
                     Err(err) => self.error = Some(anyhow::Error::msg(err)),
                 };
                 true
@@ -48,18 +61,51 @@ impl Component for Locks {
                 self.init(ctx);
                 true
             }
+            Msg::Next => {
+                self.previous_pages.push(self.page_token.clone());
+                self.page_token = self.next_page_token.clone();
+                self.init(ctx);
+                false
+            }
+            Msg::Previous => {
+                self.page_token = self.previous_pages.pop().unwrap_or_default();
+                self.init(ctx);
+                false
+            }
             Msg::Update((name, state)) => {
                 let client = self.client.clone();
                 let link = ctx.link().clone();
                 spawn_local(async move {
                     let _resp = match state {
-                        false => client.unlock(name.as_str()).await,
+                        false => client.unlock(name.as_str(), false).await,
                         true => client.lock(name.as_str()).await,
                     };
                     link.send_message(Msg::Reload);
                 });
                 false
             }
+            Msg::ForceUnlock(name) => {
+                let confirmed = web_sys::window()
+                    .and_then(|window| {
+                        window
+                            .confirm_with_message(&format!(
+                                "Release {} for whoever holds it?",
+                                name
+                            ))
+                            .ok()
+                    })
+                    .unwrap_or(false);
+                if !confirmed {
+                    return false;
+                }
+                let client = self.client.clone();
+                let link = ctx.link().clone();
+                spawn_local(async move {
+                    let _resp = client.unlock(name.as_str(), true).await;
+                    link.send_message(Msg::Reload);
+                });
+                false
+            }
             Msg::Remove(name) => {
                 let link = ctx.link().clone();
                 let client = self.client.clone();
@@ -125,6 +171,8 @@ impl Component for Locks {
                         <tr>
                             <th>{"Name"}</th>
                             <th>{"State"}</th>
+                            <th>{"Holder"}</th>
+                            <th>{"Description"}</th>
                             <th>{"Action"}</th>
                         </tr>
                     </thead>
@@ -133,8 +181,12 @@ impl Component for Locks {
                             self.locks.iter().map(|lock| {
                                 let t_name = lock.name.clone().to_string();
                                 let r_name = t_name.clone();
+                                let f_name = t_name.clone();
                                 let state = lock.state;
                                 let toggle = link.callback(move |_| Msg::Update((t_name.clone(), !state)));
+                                let force = link.callback(move |_| Msg::ForceUnlock(f_name.clone()));
+                                // Locks someone else holds only open up when forced.
+                                let held_here = lock.get_holders().iter().any(|holder| &holder[..] == HOLDER);
                                 let remove = link.callback(move |_| Msg::Remove(r_name.clone()));
                                 html!{
                                 <tr>
@@ -142,19 +194,62 @@ impl Component for Locks {
                                     <th>
                                     {
                                         match lock.state {
+                                            _ if lock.permits > 0 => html!{
+                                                <span class="tag is-info is-light">
+                                                    {format!("{}/{} permits", lock.used, lock.permits)}
+                                                </span>
+                                            },
                                             false => html!{
                                                 <span class="tag is-success is-light">{"Unlocked"}</span>
                                             },
+                                            true if lock.get_mode() == Mode::Shared => html!{
+                                                <span class="tag is-warning is-light">{"Shared"}</span>
+                                            },
                                             true => html!{
                                                 <span class="tag is-danger is-light">{"Locked"}</span>
                                             }
                                         }
                                     }
                                     </th>
+                                    <th>{ lock.get_holders().iter().map(|holder| holder.to_string()).collect::<Vec<_>>().join(", ") }</th>
                                     <th>
-                                        <button class="button is-warning mr-3" onclick={toggle}>
-                                            {match lock.state { false => "Lock", true => "Unlock"}}
-                                        </button>
+                                        { lock.get_metadata().get_description().to_string() }
+                                        {
+                                            match lock.get_metadata().get_payload() {
+                                                payload if payload.is_empty() => html!{},
+                                                payload => html!{
+                                                    <pre class="mt-1 p-1">
+                                                        {
+                                                            match std::str::from_utf8(payload) {
+                                                                Ok(text) => text.to_string(),
+                                                                Err(_) => format!("{} bytes of binary payload", payload.len()),
+                                                            }
+                                                        }
+                                                    </pre>
+                                                },
+                                            }
+                                        }
+                                        {
+                                            lock.get_metadata().get_labels().iter().map(|(key, value)| html!{
+                                                <span class="tag is-light ml-1">{format!("{}={}", key, value)}</span>
+                                            }).collect::<Html>()
+                                        }
+                                    </th>
+                                    <th>
+                                        {
+                                            match lock.state && !held_here {
+                                                true => html!{
+                                                    <button class="button is-warning mr-3" onclick={force}>
+                                                        {"Force unlock"}
+                                                    </button>
+                                                },
+                                                false => html!{
+                                                    <button class="button is-warning mr-3" onclick={toggle}>
+                                                        {match lock.state { false => "Lock", true => "Unlock"}}
+                                                    </button>
+                                                },
+                                            }
+                                        }
                                         <button class="button is-danger" onclick={remove}>
                                             {"Remove"}
                                         </button>
@@ -164,6 +259,22 @@ impl Component for Locks {
                         }
                     </tbody>
                 </table>
+                <nav class="pagination is-centered px-3">
+                    <button
+                        class="button pagination-previous"
+                        disabled={self.previous_pages.is_empty()}
+                        onclick={link.callback(|_| Msg::Previous)}
+                    >
+                        {"Previous"}
+                    </button>
+                    <button
+                        class="button pagination-next"
+                        disabled={self.next_page_token.is_empty()}
+                        onclick={link.callback(|_| Msg::Next)}
+                    >
+                        {"Next"}
+                    </button>
+                </nav>
                 {
                     if self.locks.is_empty() {
                         html!{
@@ -186,8 +297,13 @@ impl Locks {
     fn init(&self, ctx: &Context<Self>) {
         let client = self.client.clone();
         let link = ctx.link().clone();
+        let page_token = self.page_token.clone();
         spawn_local(async move {
-            let resp = client.clone().list().await.map_err(|err| err.to_string());
+            let resp = client
+                .clone()
+                .list(page_token.as_str())
+                .await
+                .map_err(|err| err.to_string());
             link.send_message(Msg::Init(resp.clone()));
         })
     }
@@ -139,23 +139,2780 @@ impl ::protobuf::reflect::ProtobufValue for Empty {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct Metadata {
+    // message fields
+    pub labels: ::std::collections::HashMap<::protobuf::Chars, ::protobuf::Chars>,
+    pub description: ::protobuf::Chars,
+    pub payload: ::bytes::Bytes,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Metadata {
+    fn default() -> &'a Metadata {
+        <Metadata as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Metadata {
+    pub fn new() -> Metadata {
+        ::std::default::Default::default()
+    }
+
+    // repeated .api.grpc.Metadata.LabelsEntry labels = 1;
+
+
+    pub fn get_labels(&self) -> &::std::collections::HashMap<::protobuf::Chars, ::protobuf::Chars> {
+        &self.labels
+    }
+    pub fn clear_labels(&mut self) {
+        self.labels.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_labels(&mut self, v: ::std::collections::HashMap<::protobuf::Chars, ::protobuf::Chars>) {
+        self.labels = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_labels(&mut self) -> &mut ::std::collections::HashMap<::protobuf::Chars, ::protobuf::Chars> {
+        &mut self.labels
+    }
+
+    // Take field
+    pub fn take_labels(&mut self) -> ::std::collections::HashMap<::protobuf::Chars, ::protobuf::Chars> {
+        ::std::mem::replace(&mut self.labels, ::std::collections::HashMap::new())
+    }
+
+    // string description = 2;
+
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+    pub fn clear_description(&mut self) {
+        ::protobuf::Clear::clear(&mut self.description);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_description(&mut self, v: ::protobuf::Chars) {
+        self.description = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_description(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.description
+    }
+
+    // Take field
+    pub fn take_description(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.description, ::protobuf::Chars::new())
+    }
+
+    // bytes payload = 3;
+
+
+    pub fn get_payload(&self) -> &[u8] {
+        &self.payload
+    }
+    pub fn clear_payload(&mut self) {
+        self.payload.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_payload(&mut self, v: ::bytes::Bytes) {
+        self.payload = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_payload(&mut self) -> &mut ::bytes::Bytes {
+        &mut self.payload
+    }
+
+    // Take field
+    pub fn take_payload(&mut self) -> ::bytes::Bytes {
+        ::std::mem::replace(&mut self.payload, ::bytes::Bytes::new())
+    }
+}
+
+impl ::protobuf::Message for Metadata {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeCarllercheChars, ::protobuf::types::ProtobufTypeCarllercheChars>(wire_type, is, &mut self.labels)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.description)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_bytes_into(wire_type, is, &mut self.payload)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeCarllercheChars, ::protobuf::types::ProtobufTypeCarllercheChars>(1, &self.labels);
+        if !self.description.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.description);
+        }
+        if !self.payload.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(3, &self.payload);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeCarllercheChars, ::protobuf::types::ProtobufTypeCarllercheChars>(1, &self.labels, os)?;
+        if !self.description.is_empty() {
+            os.write_string(2, &self.description)?;
+        }
+        if !self.payload.is_empty() {
+            os.write_bytes(3, &self.payload)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Metadata {
+        Metadata::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "labels",
+                |m: &Metadata| { &m.labels },
+                |m: &mut Metadata| { &mut m.labels },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "description",
+                |m: &Metadata| { &m.description },
+                |m: &mut Metadata| { &mut m.description },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheBytes>(
+                "payload",
+                |m: &Metadata| { &m.payload },
+                |m: &mut Metadata| { &mut m.payload },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Metadata>(
+                "Metadata",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Metadata {
+        static instance: ::protobuf::rt::LazyV2<Metadata> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Metadata::new)
+    }
+}
+
+impl ::protobuf::Clear for Metadata {
+    fn clear(&mut self) {
+        self.labels.clear();
+        ::protobuf::Clear::clear(&mut self.description);
+        self.payload.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Metadata {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Metadata {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 pub struct LockRequest {
     // message fields
     pub name: ::protobuf::Chars,
+    pub holder: ::protobuf::Chars,
+    pub force: bool,
+    pub ttl: u64,
+    pub timeout: u64,
+    pub mode: Mode,
+    pub permits: u32,
+    pub reentrant: bool,
+    pub metadata: ::protobuf::SingularPtrField<Metadata>,
+    pub subtree: bool,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a LockRequest {
+    fn default() -> &'a LockRequest {
+        <LockRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl LockRequest {
+    pub fn new() -> LockRequest {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::protobuf::Chars) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    }
+
+    // string holder = 2;
+
+
+    pub fn get_holder(&self) -> &str {
+        &self.holder
+    }
+    pub fn clear_holder(&mut self) {
+        ::protobuf::Clear::clear(&mut self.holder);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_holder(&mut self, v: ::protobuf::Chars) {
+        self.holder = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_holder(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.holder
+    }
+
+    // Take field
+    pub fn take_holder(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.holder, ::protobuf::Chars::new())
+    }
+
+    // bool force = 3;
+
+
+    pub fn get_force(&self) -> bool {
+        self.force
+    }
+    pub fn clear_force(&mut self) {
+        self.force = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_force(&mut self, v: bool) {
+        self.force = v;
+    }
+
+    // uint64 ttl = 4;
+
+
+    pub fn get_ttl(&self) -> u64 {
+        self.ttl
+    }
+    pub fn clear_ttl(&mut self) {
+        self.ttl = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ttl(&mut self, v: u64) {
+        self.ttl = v;
+    }
+
+    // uint64 timeout = 5;
+
+
+    pub fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+    pub fn clear_timeout(&mut self) {
+        self.timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timeout(&mut self, v: u64) {
+        self.timeout = v;
+    }
+
+    // .api.grpc.Mode mode = 6;
+
+
+    pub fn get_mode(&self) -> Mode {
+        self.mode
+    }
+    pub fn clear_mode(&mut self) {
+        self.mode = Mode::Exclusive;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_mode(&mut self, v: Mode) {
+        self.mode = v;
+    }
+
+    // uint32 permits = 7;
+
+
+    pub fn get_permits(&self) -> u32 {
+        self.permits
+    }
+    pub fn clear_permits(&mut self) {
+        self.permits = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_permits(&mut self, v: u32) {
+        self.permits = v;
+    }
+
+    // bool reentrant = 8;
+
+
+    pub fn get_reentrant(&self) -> bool {
+        self.reentrant
+    }
+    pub fn clear_reentrant(&mut self) {
+        self.reentrant = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_reentrant(&mut self, v: bool) {
+        self.reentrant = v;
+    }
+
+    // .api.grpc.Metadata metadata = 9;
+
+
+    pub fn get_metadata(&self) -> &Metadata {
+        self.metadata.as_ref().unwrap_or_else(|| <Metadata as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_metadata(&mut self) {
+        self.metadata.clear();
+    }
+
+    pub fn has_metadata(&self) -> bool {
+        self.metadata.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_metadata(&mut self, v: Metadata) {
+        self.metadata = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_metadata(&mut self) -> &mut Metadata {
+        if self.metadata.is_none() {
+            self.metadata.set_default();
+        }
+        self.metadata.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_metadata(&mut self) -> Metadata {
+        self.metadata.take().unwrap_or_else(|| Metadata::new())
+    }
+
+    // bool subtree = 10;
+
+
+    pub fn get_subtree(&self) -> bool {
+        self.subtree
+    }
+    pub fn clear_subtree(&mut self) {
+        self.subtree = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_subtree(&mut self, v: bool) {
+        self.subtree = v;
+    }
+}
+
+impl ::protobuf::Message for LockRequest {
+    fn is_initialized(&self) -> bool {
+        for v in &self.metadata {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.holder)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.force = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.ttl = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.timeout = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.mode, 6, &mut self.unknown_fields)?
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.permits = tmp;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.reentrant = tmp;
+                },
+                9 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.metadata)?;
+                },
+                10 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.subtree = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if !self.holder.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.holder);
+        }
+        if self.force != false {
+            my_size += 2;
+        }
+        if self.ttl != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.ttl, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.timeout != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.mode != Mode::Exclusive {
+            my_size += ::protobuf::rt::enum_size(6, self.mode);
+        }
+        if self.permits != 0 {
+            my_size += ::protobuf::rt::value_size(7, self.permits, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.reentrant != false {
+            my_size += 2;
+        }
+        if let Some(ref v) = self.metadata.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.subtree != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if !self.holder.is_empty() {
+            os.write_string(2, &self.holder)?;
+        }
+        if self.force != false {
+            os.write_bool(3, self.force)?;
+        }
+        if self.ttl != 0 {
+            os.write_uint64(4, self.ttl)?;
+        }
+        if self.timeout != 0 {
+            os.write_uint64(5, self.timeout)?;
+        }
+        if self.mode != Mode::Exclusive {
+            os.write_enum(6, ::protobuf::ProtobufEnum::value(&self.mode))?;
+        }
+        if self.permits != 0 {
+            os.write_uint32(7, self.permits)?;
+        }
+        if self.reentrant != false {
+            os.write_bool(8, self.reentrant)?;
+        }
+        if let Some(ref v) = self.metadata.as_ref() {
+            os.write_tag(9, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if self.subtree != false {
+            os.write_bool(10, self.subtree)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> LockRequest {
+        LockRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "name",
+                |m: &LockRequest| { &m.name },
+                |m: &mut LockRequest| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "holder",
+                |m: &LockRequest| { &m.holder },
+                |m: &mut LockRequest| { &mut m.holder },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "force",
+                |m: &LockRequest| { &m.force },
+                |m: &mut LockRequest| { &mut m.force },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "ttl",
+                |m: &LockRequest| { &m.ttl },
+                |m: &mut LockRequest| { &mut m.ttl },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "timeout",
+                |m: &LockRequest| { &m.timeout },
+                |m: &mut LockRequest| { &mut m.timeout },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Mode>>(
+                "mode",
+                |m: &LockRequest| { &m.mode },
+                |m: &mut LockRequest| { &mut m.mode },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "permits",
+                |m: &LockRequest| { &m.permits },
+                |m: &mut LockRequest| { &mut m.permits },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "reentrant",
+                |m: &LockRequest| { &m.reentrant },
+                |m: &mut LockRequest| { &mut m.reentrant },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Metadata>>(
+                "metadata",
+                |m: &LockRequest| { &m.metadata },
+                |m: &mut LockRequest| { &mut m.metadata },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "subtree",
+                |m: &LockRequest| { &m.subtree },
+                |m: &mut LockRequest| { &mut m.subtree },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<LockRequest>(
+                "LockRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static LockRequest {
+        static instance: ::protobuf::rt::LazyV2<LockRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(LockRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for LockRequest {
+    fn clear(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+        ::protobuf::Clear::clear(&mut self.holder);
+        self.force = false;
+        self.ttl = 0;
+        self.timeout = 0;
+        self.mode = Mode::Exclusive;
+        self.permits = 0;
+        self.reentrant = false;
+        self.metadata.clear();
+        self.subtree = false;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for LockRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for LockRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ListRequest {
+    // message fields
+    pub prefix: ::protobuf::Chars,
+    pub state: ListRequest_StateFilter,
+    pub page_size: u32,
+    pub page_token: ::protobuf::Chars,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a ListRequest {
+    fn default() -> &'a ListRequest {
+        <ListRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ListRequest {
+    pub fn new() -> ListRequest {
+        ::std::default::Default::default()
+    }
+
+    // string prefix = 1;
+
+
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+    pub fn clear_prefix(&mut self) {
+        ::protobuf::Clear::clear(&mut self.prefix);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_prefix(&mut self, v: ::protobuf::Chars) {
+        self.prefix = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_prefix(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.prefix
+    }
+
+    // Take field
+    pub fn take_prefix(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.prefix, ::protobuf::Chars::new())
+    }
+
+    // .api.grpc.ListRequest.StateFilter state = 2;
+
+
+    pub fn get_state(&self) -> ListRequest_StateFilter {
+        self.state
+    }
+    pub fn clear_state(&mut self) {
+        self.state = ListRequest_StateFilter::Any;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_state(&mut self, v: ListRequest_StateFilter) {
+        self.state = v;
+    }
+
+    // uint32 page_size = 3;
+
+
+    pub fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+    pub fn clear_page_size(&mut self) {
+        self.page_size = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_page_size(&mut self, v: u32) {
+        self.page_size = v;
+    }
+
+    // string page_token = 4;
+
+
+    pub fn get_page_token(&self) -> &str {
+        &self.page_token
+    }
+    pub fn clear_page_token(&mut self) {
+        ::protobuf::Clear::clear(&mut self.page_token);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_page_token(&mut self, v: ::protobuf::Chars) {
+        self.page_token = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_page_token(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.page_token
+    }
+
+    // Take field
+    pub fn take_page_token(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.page_token, ::protobuf::Chars::new())
+    }
+}
+
+impl ::protobuf::Message for ListRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.prefix)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.state, 2, &mut self.unknown_fields)?
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.page_size = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.page_token)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.prefix.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.prefix);
+        }
+        if self.state != ListRequest_StateFilter::Any {
+            my_size += ::protobuf::rt::enum_size(2, self.state);
+        }
+        if self.page_size != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.page_size, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.page_token.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.page_token);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.prefix.is_empty() {
+            os.write_string(1, &self.prefix)?;
+        }
+        if self.state != ListRequest_StateFilter::Any {
+            os.write_enum(2, ::protobuf::ProtobufEnum::value(&self.state))?;
+        }
+        if self.page_size != 0 {
+            os.write_uint32(3, self.page_size)?;
+        }
+        if !self.page_token.is_empty() {
+            os.write_string(4, &self.page_token)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> ListRequest {
+        ListRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "prefix",
+                |m: &ListRequest| { &m.prefix },
+                |m: &mut ListRequest| { &mut m.prefix },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<ListRequest_StateFilter>>(
+                "state",
+                |m: &ListRequest| { &m.state },
+                |m: &mut ListRequest| { &mut m.state },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "page_size",
+                |m: &ListRequest| { &m.page_size },
+                |m: &mut ListRequest| { &mut m.page_size },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "page_token",
+                |m: &ListRequest| { &m.page_token },
+                |m: &mut ListRequest| { &mut m.page_token },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ListRequest>(
+                "ListRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static ListRequest {
+        static instance: ::protobuf::rt::LazyV2<ListRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(ListRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for ListRequest {
+    fn clear(&mut self) {
+        ::protobuf::Clear::clear(&mut self.prefix);
+        self.state = ListRequest_StateFilter::Any;
+        self.page_size = 0;
+        ::protobuf::Clear::clear(&mut self.page_token);
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ListRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ListRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum ListRequest_StateFilter {
+    Any = 0,
+    Locked = 1,
+    Unlocked = 2,
+}
+
+impl ::protobuf::ProtobufEnum for ListRequest_StateFilter {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<ListRequest_StateFilter> {
+        match value {
+            0 => ::std::option::Option::Some(ListRequest_StateFilter::Any),
+            1 => ::std::option::Option::Some(ListRequest_StateFilter::Locked),
+            2 => ::std::option::Option::Some(ListRequest_StateFilter::Unlocked),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [ListRequest_StateFilter] = &[
+            ListRequest_StateFilter::Any,
+            ListRequest_StateFilter::Locked,
+            ListRequest_StateFilter::Unlocked,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<ListRequest_StateFilter>("ListRequest.StateFilter", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for ListRequest_StateFilter {
+}
+
+impl ::std::default::Default for ListRequest_StateFilter {
+    fn default() -> Self {
+        ListRequest_StateFilter::Any
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ListRequest_StateFilter {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct LockManyRequest {
+    // message fields
+    pub names: ::std::vec::Vec<::protobuf::Chars>,
+    pub holder: ::protobuf::Chars,
+    pub ttl: u64,
+    pub timeout: u64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a LockManyRequest {
+    fn default() -> &'a LockManyRequest {
+        <LockManyRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl LockManyRequest {
+    pub fn new() -> LockManyRequest {
+        ::std::default::Default::default()
+    }
+
+    // repeated string names = 1;
+
+
+    pub fn get_names(&self) -> &[::protobuf::Chars] {
+        &self.names
+    }
+    pub fn clear_names(&mut self) {
+        self.names.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_names(&mut self, v: ::std::vec::Vec<::protobuf::Chars>) {
+        self.names = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_names(&mut self) -> &mut ::std::vec::Vec<::protobuf::Chars> {
+        &mut self.names
+    }
+
+    // Take field
+    pub fn take_names(&mut self) -> ::std::vec::Vec<::protobuf::Chars> {
+        ::std::mem::replace(&mut self.names, ::std::vec::Vec::new())
+    }
+
+    // string holder = 2;
+
+
+    pub fn get_holder(&self) -> &str {
+        &self.holder
+    }
+    pub fn clear_holder(&mut self) {
+        ::protobuf::Clear::clear(&mut self.holder);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_holder(&mut self, v: ::protobuf::Chars) {
+        self.holder = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_holder(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.holder
+    }
+
+    // Take field
+    pub fn take_holder(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.holder, ::protobuf::Chars::new())
+    }
+
+    // uint64 ttl = 3;
+
+
+    pub fn get_ttl(&self) -> u64 {
+        self.ttl
+    }
+    pub fn clear_ttl(&mut self) {
+        self.ttl = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ttl(&mut self, v: u64) {
+        self.ttl = v;
+    }
+
+    // uint64 timeout = 4;
+
+
+    pub fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+    pub fn clear_timeout(&mut self) {
+        self.timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timeout(&mut self, v: u64) {
+        self.timeout = v;
+    }
+}
+
+impl ::protobuf::Message for LockManyRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_carllerche_string_into(wire_type, is, &mut self.names)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.holder)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.ttl = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.timeout = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.names {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        if !self.holder.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.holder);
+        }
+        if self.ttl != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.ttl, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.timeout != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.names {
+            os.write_string(1, &v)?;
+        };
+        if !self.holder.is_empty() {
+            os.write_string(2, &self.holder)?;
+        }
+        if self.ttl != 0 {
+            os.write_uint64(3, self.ttl)?;
+        }
+        if self.timeout != 0 {
+            os.write_uint64(4, self.timeout)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> LockManyRequest {
+        LockManyRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "names",
+                |m: &LockManyRequest| { &m.names },
+                |m: &mut LockManyRequest| { &mut m.names },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "holder",
+                |m: &LockManyRequest| { &m.holder },
+                |m: &mut LockManyRequest| { &mut m.holder },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "ttl",
+                |m: &LockManyRequest| { &m.ttl },
+                |m: &mut LockManyRequest| { &mut m.ttl },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "timeout",
+                |m: &LockManyRequest| { &m.timeout },
+                |m: &mut LockManyRequest| { &mut m.timeout },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<LockManyRequest>(
+                "LockManyRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static LockManyRequest {
+        static instance: ::protobuf::rt::LazyV2<LockManyRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(LockManyRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for LockManyRequest {
+    fn clear(&mut self) {
+        self.names.clear();
+        ::protobuf::Clear::clear(&mut self.holder);
+        self.ttl = 0;
+        self.timeout = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for LockManyRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for LockManyRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct LockManyResponse {
+    // message fields
+    pub tokens: ::std::vec::Vec<u64>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a LockManyResponse {
+    fn default() -> &'a LockManyResponse {
+        <LockManyResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl LockManyResponse {
+    pub fn new() -> LockManyResponse {
+        ::std::default::Default::default()
+    }
+
+    // repeated uint64 tokens = 1;
+
+
+    pub fn get_tokens(&self) -> &[u64] {
+        &self.tokens
+    }
+    pub fn clear_tokens(&mut self) {
+        self.tokens.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tokens(&mut self, v: ::std::vec::Vec<u64>) {
+        self.tokens = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_tokens(&mut self) -> &mut ::std::vec::Vec<u64> {
+        &mut self.tokens
+    }
+
+    // Take field
+    pub fn take_tokens(&mut self) -> ::std::vec::Vec<u64> {
+        ::std::mem::replace(&mut self.tokens, ::std::vec::Vec::new())
+    }
+}
+
+impl ::protobuf::Message for LockManyResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_uint64_into(wire_type, is, &mut self.tokens)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.tokens {
+            my_size += ::protobuf::rt::value_size(1, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.tokens {
+            os.write_uint64(1, *v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> LockManyResponse {
+        LockManyResponse::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "tokens",
+                |m: &LockManyResponse| { &m.tokens },
+                |m: &mut LockManyResponse| { &mut m.tokens },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<LockManyResponse>(
+                "LockManyResponse",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static LockManyResponse {
+        static instance: ::protobuf::rt::LazyV2<LockManyResponse> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(LockManyResponse::new)
+    }
+}
+
+impl ::protobuf::Clear for LockManyResponse {
+    fn clear(&mut self) {
+        self.tokens.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for LockManyResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for LockManyResponse {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ElectionRequest {
+    // message fields
+    pub name: ::protobuf::Chars,
+    pub candidate: ::protobuf::Chars,
+    pub ttl: u64,
+    pub timeout: u64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a ElectionRequest {
+    fn default() -> &'a ElectionRequest {
+        <ElectionRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ElectionRequest {
+    pub fn new() -> ElectionRequest {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::protobuf::Chars) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    }
+
+    // string candidate = 2;
+
+
+    pub fn get_candidate(&self) -> &str {
+        &self.candidate
+    }
+    pub fn clear_candidate(&mut self) {
+        ::protobuf::Clear::clear(&mut self.candidate);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_candidate(&mut self, v: ::protobuf::Chars) {
+        self.candidate = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_candidate(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.candidate
+    }
+
+    // Take field
+    pub fn take_candidate(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.candidate, ::protobuf::Chars::new())
+    }
+
+    // uint64 ttl = 3;
+
+
+    pub fn get_ttl(&self) -> u64 {
+        self.ttl
+    }
+    pub fn clear_ttl(&mut self) {
+        self.ttl = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ttl(&mut self, v: u64) {
+        self.ttl = v;
+    }
+
+    // uint64 timeout = 4;
+
+
+    pub fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+    pub fn clear_timeout(&mut self) {
+        self.timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timeout(&mut self, v: u64) {
+        self.timeout = v;
+    }
+}
+
+impl ::protobuf::Message for ElectionRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.candidate)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.ttl = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.timeout = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if !self.candidate.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.candidate);
+        }
+        if self.ttl != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.ttl, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.timeout != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if !self.candidate.is_empty() {
+            os.write_string(2, &self.candidate)?;
+        }
+        if self.ttl != 0 {
+            os.write_uint64(3, self.ttl)?;
+        }
+        if self.timeout != 0 {
+            os.write_uint64(4, self.timeout)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> ElectionRequest {
+        ElectionRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "name",
+                |m: &ElectionRequest| { &m.name },
+                |m: &mut ElectionRequest| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "candidate",
+                |m: &ElectionRequest| { &m.candidate },
+                |m: &mut ElectionRequest| { &mut m.candidate },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "ttl",
+                |m: &ElectionRequest| { &m.ttl },
+                |m: &mut ElectionRequest| { &mut m.ttl },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "timeout",
+                |m: &ElectionRequest| { &m.timeout },
+                |m: &mut ElectionRequest| { &mut m.timeout },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ElectionRequest>(
+                "ElectionRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static ElectionRequest {
+        static instance: ::protobuf::rt::LazyV2<ElectionRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(ElectionRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for ElectionRequest {
+    fn clear(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+        ::protobuf::Clear::clear(&mut self.candidate);
+        self.ttl = 0;
+        self.timeout = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ElectionRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ElectionRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct LeaderResponse {
+    // message fields
+    pub name: ::protobuf::Chars,
+    pub leader: ::protobuf::Chars,
+    pub term: u64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a LeaderResponse {
+    fn default() -> &'a LeaderResponse {
+        <LeaderResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl LeaderResponse {
+    pub fn new() -> LeaderResponse {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::protobuf::Chars) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    }
+
+    // string leader = 2;
+
+
+    pub fn get_leader(&self) -> &str {
+        &self.leader
+    }
+    pub fn clear_leader(&mut self) {
+        ::protobuf::Clear::clear(&mut self.leader);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_leader(&mut self, v: ::protobuf::Chars) {
+        self.leader = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_leader(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.leader
+    }
+
+    // Take field
+    pub fn take_leader(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.leader, ::protobuf::Chars::new())
+    }
+
+    // uint64 term = 3;
+
+
+    pub fn get_term(&self) -> u64 {
+        self.term
+    }
+    pub fn clear_term(&mut self) {
+        self.term = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_term(&mut self, v: u64) {
+        self.term = v;
+    }
+}
+
+impl ::protobuf::Message for LeaderResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.leader)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.term = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if !self.leader.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.leader);
+        }
+        if self.term != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.term, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if !self.leader.is_empty() {
+            os.write_string(2, &self.leader)?;
+        }
+        if self.term != 0 {
+            os.write_uint64(3, self.term)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> LeaderResponse {
+        LeaderResponse::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "name",
+                |m: &LeaderResponse| { &m.name },
+                |m: &mut LeaderResponse| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "leader",
+                |m: &LeaderResponse| { &m.leader },
+                |m: &mut LeaderResponse| { &mut m.leader },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "term",
+                |m: &LeaderResponse| { &m.term },
+                |m: &mut LeaderResponse| { &mut m.term },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<LeaderResponse>(
+                "LeaderResponse",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static LeaderResponse {
+        static instance: ::protobuf::rt::LazyV2<LeaderResponse> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(LeaderResponse::new)
+    }
+}
+
+impl ::protobuf::Clear for LeaderResponse {
+    fn clear(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+        ::protobuf::Clear::clear(&mut self.leader);
+        self.term = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for LeaderResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for LeaderResponse {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct BarrierRequest {
+    // message fields
+    pub name: ::protobuf::Chars,
+    pub participant: ::protobuf::Chars,
+    pub parties: u32,
+    pub timeout: u64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a BarrierRequest {
+    fn default() -> &'a BarrierRequest {
+        <BarrierRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl BarrierRequest {
+    pub fn new() -> BarrierRequest {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::protobuf::Chars) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    }
+
+    // string participant = 2;
+
+
+    pub fn get_participant(&self) -> &str {
+        &self.participant
+    }
+    pub fn clear_participant(&mut self) {
+        ::protobuf::Clear::clear(&mut self.participant);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_participant(&mut self, v: ::protobuf::Chars) {
+        self.participant = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_participant(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.participant
+    }
+
+    // Take field
+    pub fn take_participant(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.participant, ::protobuf::Chars::new())
+    }
+
+    // uint32 parties = 3;
+
+
+    pub fn get_parties(&self) -> u32 {
+        self.parties
+    }
+    pub fn clear_parties(&mut self) {
+        self.parties = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_parties(&mut self, v: u32) {
+        self.parties = v;
+    }
+
+    // uint64 timeout = 4;
+
+
+    pub fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+    pub fn clear_timeout(&mut self) {
+        self.timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timeout(&mut self, v: u64) {
+        self.timeout = v;
+    }
+}
+
+impl ::protobuf::Message for BarrierRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.participant)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.parties = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.timeout = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if !self.participant.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.participant);
+        }
+        if self.parties != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.parties, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.timeout != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if !self.participant.is_empty() {
+            os.write_string(2, &self.participant)?;
+        }
+        if self.parties != 0 {
+            os.write_uint32(3, self.parties)?;
+        }
+        if self.timeout != 0 {
+            os.write_uint64(4, self.timeout)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> BarrierRequest {
+        BarrierRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "name",
+                |m: &BarrierRequest| { &m.name },
+                |m: &mut BarrierRequest| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "participant",
+                |m: &BarrierRequest| { &m.participant },
+                |m: &mut BarrierRequest| { &mut m.participant },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "parties",
+                |m: &BarrierRequest| { &m.parties },
+                |m: &mut BarrierRequest| { &mut m.parties },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "timeout",
+                |m: &BarrierRequest| { &m.timeout },
+                |m: &mut BarrierRequest| { &mut m.timeout },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<BarrierRequest>(
+                "BarrierRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static BarrierRequest {
+        static instance: ::protobuf::rt::LazyV2<BarrierRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(BarrierRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for BarrierRequest {
+    fn clear(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+        ::protobuf::Clear::clear(&mut self.participant);
+        self.parties = 0;
+        self.timeout = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for BarrierRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BarrierRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct BarrierResponse {
+    // message fields
+    pub name: ::protobuf::Chars,
+    pub parties: u32,
+    pub arrived: u32,
+    pub generation: u64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a BarrierResponse {
+    fn default() -> &'a BarrierResponse {
+        <BarrierResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl BarrierResponse {
+    pub fn new() -> BarrierResponse {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::protobuf::Chars) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    }
+
+    // uint32 parties = 2;
+
+
+    pub fn get_parties(&self) -> u32 {
+        self.parties
+    }
+    pub fn clear_parties(&mut self) {
+        self.parties = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_parties(&mut self, v: u32) {
+        self.parties = v;
+    }
+
+    // uint32 arrived = 3;
+
+
+    pub fn get_arrived(&self) -> u32 {
+        self.arrived
+    }
+    pub fn clear_arrived(&mut self) {
+        self.arrived = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_arrived(&mut self, v: u32) {
+        self.arrived = v;
+    }
+
+    // uint64 generation = 4;
+
+
+    pub fn get_generation(&self) -> u64 {
+        self.generation
+    }
+    pub fn clear_generation(&mut self) {
+        self.generation = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_generation(&mut self, v: u64) {
+        self.generation = v;
+    }
+}
+
+impl ::protobuf::Message for BarrierResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.parties = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.arrived = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.generation = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if self.parties != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.parties, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.arrived != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.arrived, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.generation != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.generation, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if self.parties != 0 {
+            os.write_uint32(2, self.parties)?;
+        }
+        if self.arrived != 0 {
+            os.write_uint32(3, self.arrived)?;
+        }
+        if self.generation != 0 {
+            os.write_uint64(4, self.generation)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> BarrierResponse {
+        BarrierResponse::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "name",
+                |m: &BarrierResponse| { &m.name },
+                |m: &mut BarrierResponse| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "parties",
+                |m: &BarrierResponse| { &m.parties },
+                |m: &mut BarrierResponse| { &mut m.parties },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "arrived",
+                |m: &BarrierResponse| { &m.arrived },
+                |m: &mut BarrierResponse| { &mut m.arrived },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "generation",
+                |m: &BarrierResponse| { &m.generation },
+                |m: &mut BarrierResponse| { &mut m.generation },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<BarrierResponse>(
+                "BarrierResponse",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static BarrierResponse {
+        static instance: ::protobuf::rt::LazyV2<BarrierResponse> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(BarrierResponse::new)
+    }
+}
+
+impl ::protobuf::Clear for BarrierResponse {
+    fn clear(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+        self.parties = 0;
+        self.arrived = 0;
+        self.generation = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for BarrierResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BarrierResponse {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct LatchRequest {
+    // message fields
+    pub name: ::protobuf::Chars,
+    pub count: u32,
+    pub timeout: u64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a LatchRequest {
+    fn default() -> &'a LatchRequest {
+        <LatchRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl LatchRequest {
+    pub fn new() -> LatchRequest {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::protobuf::Chars) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    }
+
+    // uint32 count = 2;
+
+
+    pub fn get_count(&self) -> u32 {
+        self.count
+    }
+    pub fn clear_count(&mut self) {
+        self.count = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_count(&mut self, v: u32) {
+        self.count = v;
+    }
+
+    // uint64 timeout = 3;
+
+
+    pub fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+    pub fn clear_timeout(&mut self) {
+        self.timeout = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timeout(&mut self, v: u64) {
+        self.timeout = v;
+    }
+}
+
+impl ::protobuf::Message for LatchRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.count = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.timeout = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if self.count != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.count, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.timeout != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.timeout, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if self.count != 0 {
+            os.write_uint32(2, self.count)?;
+        }
+        if self.timeout != 0 {
+            os.write_uint64(3, self.timeout)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> LatchRequest {
+        LatchRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "name",
+                |m: &LatchRequest| { &m.name },
+                |m: &mut LatchRequest| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "count",
+                |m: &LatchRequest| { &m.count },
+                |m: &mut LatchRequest| { &mut m.count },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "timeout",
+                |m: &LatchRequest| { &m.timeout },
+                |m: &mut LatchRequest| { &mut m.timeout },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<LatchRequest>(
+                "LatchRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static LatchRequest {
+        static instance: ::protobuf::rt::LazyV2<LatchRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(LatchRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for LatchRequest {
+    fn clear(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+        self.count = 0;
+        self.timeout = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for LatchRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for LatchRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct LatchResponse {
+    // message fields
+    pub name: ::protobuf::Chars,
+    pub count: u32,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a LockRequest {
-    fn default() -> &'a LockRequest {
-        <LockRequest as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a LatchResponse {
+    fn default() -> &'a LatchResponse {
+        <LatchResponse as ::protobuf::Message>::default_instance()
     }
 }
 
-impl LockRequest {
-    pub fn new() -> LockRequest {
+impl LatchResponse {
+    pub fn new() -> LatchResponse {
         ::std::default::Default::default()
     }
 
@@ -184,9 +2941,24 @@ impl LockRequest {
     pub fn take_name(&mut self) -> ::protobuf::Chars {
         ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
     }
+
+    // uint32 count = 2;
+
+
+    pub fn get_count(&self) -> u32 {
+        self.count
+    }
+    pub fn clear_count(&mut self) {
+        self.count = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_count(&mut self, v: u32) {
+        self.count = v;
+    }
 }
 
-impl ::protobuf::Message for LockRequest {
+impl ::protobuf::Message for LatchResponse {
     fn is_initialized(&self) -> bool {
         true
     }
@@ -198,6 +2970,13 @@ impl ::protobuf::Message for LockRequest {
                 1 => {
                     ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.name)?;
                 },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.count = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -213,6 +2992,9 @@ impl ::protobuf::Message for LockRequest {
         if !self.name.is_empty() {
             my_size += ::protobuf::rt::string_size(1, &self.name);
         }
+        if self.count != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.count, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -222,6 +3004,9 @@ impl ::protobuf::Message for LockRequest {
         if !self.name.is_empty() {
             os.write_string(1, &self.name)?;
         }
+        if self.count != 0 {
+            os.write_uint32(2, self.count)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -252,8 +3037,8 @@ impl ::protobuf::Message for LockRequest {
         Self::descriptor_static()
     }
 
-    fn new() -> LockRequest {
-        LockRequest::new()
+    fn new() -> LatchResponse {
+        LatchResponse::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -262,37 +3047,43 @@ impl ::protobuf::Message for LockRequest {
             let mut fields = ::std::vec::Vec::new();
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
                 "name",
-                |m: &LockRequest| { &m.name },
-                |m: &mut LockRequest| { &mut m.name },
+                |m: &LatchResponse| { &m.name },
+                |m: &mut LatchResponse| { &mut m.name },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<LockRequest>(
-                "LockRequest",
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "count",
+                |m: &LatchResponse| { &m.count },
+                |m: &mut LatchResponse| { &mut m.count },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<LatchResponse>(
+                "LatchResponse",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static LockRequest {
-        static instance: ::protobuf::rt::LazyV2<LockRequest> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(LockRequest::new)
+    fn default_instance() -> &'static LatchResponse {
+        static instance: ::protobuf::rt::LazyV2<LatchResponse> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(LatchResponse::new)
     }
 }
 
-impl ::protobuf::Clear for LockRequest {
+impl ::protobuf::Clear for LatchResponse {
     fn clear(&mut self) {
         ::protobuf::Clear::clear(&mut self.name);
+        self.count = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for LockRequest {
+impl ::std::fmt::Debug for LatchResponse {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for LockRequest {
+impl ::protobuf::reflect::ProtobufValue for LatchResponse {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
@@ -300,6 +3091,9 @@ impl ::protobuf::reflect::ProtobufValue for LockRequest {
 
 #[derive(PartialEq,Clone,Default)]
 pub struct LockResponse {
+    // message fields
+    pub position: u32,
+    pub metadata: ::protobuf::SingularPtrField<Metadata>,
     // message oneof groups
     pub body: ::std::option::Option<LockResponse_oneof_body>,
     // special fields
@@ -317,6 +3111,7 @@ impl<'a> ::std::default::Default for &'a LockResponse {
 pub enum LockResponse_oneof_body {
     error(::protobuf::Chars),
     state(bool),
+    token(u64),
 }
 
 impl LockResponse {
@@ -397,10 +3192,88 @@ impl LockResponse {
     pub fn set_state(&mut self, v: bool) {
         self.body = ::std::option::Option::Some(LockResponse_oneof_body::state(v))
     }
+
+    // uint64 token = 3;
+
+
+    pub fn get_token(&self) -> u64 {
+        match self.body {
+            ::std::option::Option::Some(LockResponse_oneof_body::token(v)) => v,
+            _ => 0,
+        }
+    }
+    pub fn clear_token(&mut self) {
+        self.body = ::std::option::Option::None;
+    }
+
+    pub fn has_token(&self) -> bool {
+        match self.body {
+            ::std::option::Option::Some(LockResponse_oneof_body::token(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_token(&mut self, v: u64) {
+        self.body = ::std::option::Option::Some(LockResponse_oneof_body::token(v))
+    }
+
+    // uint32 position = 4;
+
+
+    pub fn get_position(&self) -> u32 {
+        self.position
+    }
+    pub fn clear_position(&mut self) {
+        self.position = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_position(&mut self, v: u32) {
+        self.position = v;
+    }
+
+    // .api.grpc.Metadata metadata = 5;
+
+
+    pub fn get_metadata(&self) -> &Metadata {
+        self.metadata.as_ref().unwrap_or_else(|| <Metadata as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_metadata(&mut self) {
+        self.metadata.clear();
+    }
+
+    pub fn has_metadata(&self) -> bool {
+        self.metadata.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_metadata(&mut self, v: Metadata) {
+        self.metadata = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_metadata(&mut self) -> &mut Metadata {
+        if self.metadata.is_none() {
+            self.metadata.set_default();
+        }
+        self.metadata.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_metadata(&mut self) -> Metadata {
+        self.metadata.take().unwrap_or_else(|| Metadata::new())
+    }
 }
 
 impl ::protobuf::Message for LockResponse {
     fn is_initialized(&self) -> bool {
+        for v in &self.metadata {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -420,6 +3293,22 @@ impl ::protobuf::Message for LockResponse {
                     }
                     self.body = ::std::option::Option::Some(LockResponse_oneof_body::state(is.read_bool()?));
                 },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.body = ::std::option::Option::Some(LockResponse_oneof_body::token(is.read_uint64()?));
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.position = tmp;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.metadata)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -432,6 +3321,13 @@ impl ::protobuf::Message for LockResponse {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
+        if self.position != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.position, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.metadata.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
         if let ::std::option::Option::Some(ref v) = self.body {
             match v {
                 &LockResponse_oneof_body::error(ref v) => {
@@ -440,6 +3336,9 @@ impl ::protobuf::Message for LockResponse {
                 &LockResponse_oneof_body::state(v) => {
                     my_size += 2;
                 },
+                &LockResponse_oneof_body::token(v) => {
+                    my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+                },
             };
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
@@ -448,6 +3347,14 @@ impl ::protobuf::Message for LockResponse {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.position != 0 {
+            os.write_uint32(4, self.position)?;
+        }
+        if let Some(ref v) = self.metadata.as_ref() {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
         if let ::std::option::Option::Some(ref v) = self.body {
             match v {
                 &LockResponse_oneof_body::error(ref v) => {
@@ -456,6 +3363,9 @@ impl ::protobuf::Message for LockResponse {
                 &LockResponse_oneof_body::state(v) => {
                     os.write_bool(2, v)?;
                 },
+                &LockResponse_oneof_body::token(v) => {
+                    os.write_uint64(3, v)?;
+                },
             };
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
@@ -506,6 +3416,21 @@ impl ::protobuf::Message for LockResponse {
                 LockResponse::has_state,
                 LockResponse::get_state,
             ));
+            fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor::<_>(
+                "token",
+                LockResponse::has_token,
+                LockResponse::get_token,
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "position",
+                |m: &LockResponse| { &m.position },
+                |m: &mut LockResponse| { &mut m.position },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Metadata>>(
+                "metadata",
+                |m: &LockResponse| { &m.metadata },
+                |m: &mut LockResponse| { &mut m.metadata },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<LockResponse>(
                 "LockResponse",
                 fields,
@@ -524,6 +3449,9 @@ impl ::protobuf::Clear for LockResponse {
     fn clear(&mut self) {
         self.body = ::std::option::Option::None;
         self.body = ::std::option::Option::None;
+        self.body = ::std::option::Option::None;
+        self.position = 0;
+        self.metadata.clear();
         self.unknown_fields.clear();
     }
 }
@@ -545,6 +3473,9 @@ pub struct LockEvent {
     // message fields
     pub name: ::protobuf::Chars,
     pub status: LockEvent_Status,
+    pub holder: ::protobuf::Chars,
+    pub description: ::protobuf::Chars,
+    pub revision: u64,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -578,28 +3509,95 @@ impl LockEvent {
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
-        &mut self.name
+    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    }
+
+    // .api.grpc.LockEvent.Status status = 2;
+
+
+    pub fn get_status(&self) -> LockEvent_Status {
+        self.status
+    }
+    pub fn clear_status(&mut self) {
+        self.status = LockEvent_Status::Created;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status(&mut self, v: LockEvent_Status) {
+        self.status = v;
+    }
+
+    // string holder = 3;
+
+
+    pub fn get_holder(&self) -> &str {
+        &self.holder
+    }
+    pub fn clear_holder(&mut self) {
+        ::protobuf::Clear::clear(&mut self.holder);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_holder(&mut self, v: ::protobuf::Chars) {
+        self.holder = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_holder(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.holder
+    }
+
+    // Take field
+    pub fn take_holder(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.holder, ::protobuf::Chars::new())
+    }
+
+    // string description = 4;
+
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+    pub fn clear_description(&mut self) {
+        ::protobuf::Clear::clear(&mut self.description);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_description(&mut self, v: ::protobuf::Chars) {
+        self.description = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_description(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.description
     }
 
     // Take field
-    pub fn take_name(&mut self) -> ::protobuf::Chars {
-        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    pub fn take_description(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.description, ::protobuf::Chars::new())
     }
 
-    // .api.grpc.LockEvent.Status status = 2;
+    // uint64 revision = 5;
 
 
-    pub fn get_status(&self) -> LockEvent_Status {
-        self.status
+    pub fn get_revision(&self) -> u64 {
+        self.revision
     }
-    pub fn clear_status(&mut self) {
-        self.status = LockEvent_Status::Created;
+    pub fn clear_revision(&mut self) {
+        self.revision = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_status(&mut self, v: LockEvent_Status) {
-        self.status = v;
+    pub fn set_revision(&mut self, v: u64) {
+        self.revision = v;
     }
 }
 
@@ -618,6 +3616,19 @@ impl ::protobuf::Message for LockEvent {
                 2 => {
                     ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.status, 2, &mut self.unknown_fields)?
                 },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.holder)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.description)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.revision = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -636,6 +3647,15 @@ impl ::protobuf::Message for LockEvent {
         if self.status != LockEvent_Status::Created {
             my_size += ::protobuf::rt::enum_size(2, self.status);
         }
+        if !self.holder.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.holder);
+        }
+        if !self.description.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.description);
+        }
+        if self.revision != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.revision, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -648,6 +3668,15 @@ impl ::protobuf::Message for LockEvent {
         if self.status != LockEvent_Status::Created {
             os.write_enum(2, ::protobuf::ProtobufEnum::value(&self.status))?;
         }
+        if !self.holder.is_empty() {
+            os.write_string(3, &self.holder)?;
+        }
+        if !self.description.is_empty() {
+            os.write_string(4, &self.description)?;
+        }
+        if self.revision != 0 {
+            os.write_uint64(5, self.revision)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -696,6 +3725,21 @@ impl ::protobuf::Message for LockEvent {
                 |m: &LockEvent| { &m.status },
                 |m: &mut LockEvent| { &mut m.status },
             ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "holder",
+                |m: &LockEvent| { &m.holder },
+                |m: &mut LockEvent| { &mut m.holder },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "description",
+                |m: &LockEvent| { &m.description },
+                |m: &mut LockEvent| { &mut m.description },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "revision",
+                |m: &LockEvent| { &m.revision },
+                |m: &mut LockEvent| { &mut m.revision },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<LockEvent>(
                 "LockEvent",
                 fields,
@@ -714,6 +3758,9 @@ impl ::protobuf::Clear for LockEvent {
     fn clear(&mut self) {
         ::protobuf::Clear::clear(&mut self.name);
         self.status = LockEvent_Status::Created;
+        ::protobuf::Clear::clear(&mut self.holder);
+        ::protobuf::Clear::clear(&mut self.description);
+        self.revision = 0;
         self.unknown_fields.clear();
     }
 }
@@ -736,6 +3783,15 @@ pub enum LockEvent_Status {
     Removed = 1,
     Unlocked = 2,
     Locked = 3,
+    Expired = 4,
+    Queued = 5,
+    Dequeued = 6,
+    Deadlock = 7,
+    Entered = 8,
+    Tripped = 9,
+    CountedDown = 10,
+    Opened = 11,
+    Lost = 12,
 }
 
 impl ::protobuf::ProtobufEnum for LockEvent_Status {
@@ -749,6 +3805,15 @@ impl ::protobuf::ProtobufEnum for LockEvent_Status {
             1 => ::std::option::Option::Some(LockEvent_Status::Removed),
             2 => ::std::option::Option::Some(LockEvent_Status::Unlocked),
             3 => ::std::option::Option::Some(LockEvent_Status::Locked),
+            4 => ::std::option::Option::Some(LockEvent_Status::Expired),
+            5 => ::std::option::Option::Some(LockEvent_Status::Queued),
+            6 => ::std::option::Option::Some(LockEvent_Status::Dequeued),
+            7 => ::std::option::Option::Some(LockEvent_Status::Deadlock),
+            8 => ::std::option::Option::Some(LockEvent_Status::Entered),
+            9 => ::std::option::Option::Some(LockEvent_Status::Tripped),
+            10 => ::std::option::Option::Some(LockEvent_Status::CountedDown),
+            11 => ::std::option::Option::Some(LockEvent_Status::Opened),
+            12 => ::std::option::Option::Some(LockEvent_Status::Lost),
             _ => ::std::option::Option::None
         }
     }
@@ -759,6 +3824,15 @@ impl ::protobuf::ProtobufEnum for LockEvent_Status {
             LockEvent_Status::Removed,
             LockEvent_Status::Unlocked,
             LockEvent_Status::Locked,
+            LockEvent_Status::Expired,
+            LockEvent_Status::Queued,
+            LockEvent_Status::Dequeued,
+            LockEvent_Status::Deadlock,
+            LockEvent_Status::Entered,
+            LockEvent_Status::Tripped,
+            LockEvent_Status::CountedDown,
+            LockEvent_Status::Opened,
+            LockEvent_Status::Lost,
         ];
         values
     }
@@ -786,6 +3860,283 @@ impl ::protobuf::reflect::ProtobufValue for LockEvent_Status {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct WatchRequest {
+    // message fields
+    pub name: ::protobuf::Chars,
+    pub prefix: ::protobuf::Chars,
+    pub statuses: ::std::vec::Vec<LockEvent_Status>,
+    pub start_revision: u64,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a WatchRequest {
+    fn default() -> &'a WatchRequest {
+        <WatchRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl WatchRequest {
+    pub fn new() -> WatchRequest {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::protobuf::Chars) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.name, ::protobuf::Chars::new())
+    }
+
+    // string prefix = 2;
+
+
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+    pub fn clear_prefix(&mut self) {
+        ::protobuf::Clear::clear(&mut self.prefix);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_prefix(&mut self, v: ::protobuf::Chars) {
+        self.prefix = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_prefix(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.prefix
+    }
+
+    // Take field
+    pub fn take_prefix(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.prefix, ::protobuf::Chars::new())
+    }
+
+    // repeated .api.grpc.LockEvent.Status statuses = 3;
+
+
+    pub fn get_statuses(&self) -> &[LockEvent_Status] {
+        &self.statuses
+    }
+    pub fn clear_statuses(&mut self) {
+        self.statuses.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_statuses(&mut self, v: ::std::vec::Vec<LockEvent_Status>) {
+        self.statuses = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_statuses(&mut self) -> &mut ::std::vec::Vec<LockEvent_Status> {
+        &mut self.statuses
+    }
+
+    // Take field
+    pub fn take_statuses(&mut self) -> ::std::vec::Vec<LockEvent_Status> {
+        ::std::mem::replace(&mut self.statuses, ::std::vec::Vec::new())
+    }
+
+    // uint64 start_revision = 4;
+
+
+    pub fn get_start_revision(&self) -> u64 {
+        self.start_revision
+    }
+    pub fn clear_start_revision(&mut self) {
+        self.start_revision = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_start_revision(&mut self, v: u64) {
+        self.start_revision = v;
+    }
+}
+
+impl ::protobuf::Message for WatchRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.prefix)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_repeated_enum_with_unknown_fields_into(wire_type, is, &mut self.statuses, 3, &mut self.unknown_fields)?
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.start_revision = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if !self.prefix.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.prefix);
+        }
+        for value in &self.statuses {
+            my_size += ::protobuf::rt::enum_size(3, *value);
+        };
+        if self.start_revision != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.start_revision, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if !self.prefix.is_empty() {
+            os.write_string(2, &self.prefix)?;
+        }
+        for v in &self.statuses {
+            os.write_enum(3, ::protobuf::ProtobufEnum::value(v))?;
+        };
+        if self.start_revision != 0 {
+            os.write_uint64(4, self.start_revision)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> WatchRequest {
+        WatchRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "name",
+                |m: &WatchRequest| { &m.name },
+                |m: &mut WatchRequest| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "prefix",
+                |m: &WatchRequest| { &m.prefix },
+                |m: &mut WatchRequest| { &mut m.prefix },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeEnum<LockEvent_Status>>(
+                "statuses",
+                |m: &WatchRequest| { &m.statuses },
+                |m: &mut WatchRequest| { &mut m.statuses },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "start_revision",
+                |m: &WatchRequest| { &m.start_revision },
+                |m: &mut WatchRequest| { &mut m.start_revision },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<WatchRequest>(
+                "WatchRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static WatchRequest {
+        static instance: ::protobuf::rt::LazyV2<WatchRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(WatchRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for WatchRequest {
+    fn clear(&mut self) {
+        ::protobuf::Clear::clear(&mut self.name);
+        ::protobuf::Clear::clear(&mut self.prefix);
+        self.statuses.clear();
+        self.start_revision = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for WatchRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for WatchRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 pub struct PeersResponse {
     // message fields
@@ -1192,6 +4543,8 @@ impl ::protobuf::reflect::ProtobufValue for PeersResponse_Peer {
 pub struct ListResponse {
     // message fields
     pub locks: ::protobuf::RepeatedField<ListResponse_Lock>,
+    pub next_page_token: ::protobuf::Chars,
+    pub revision: u64,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -1232,6 +4585,47 @@ impl ListResponse {
     pub fn take_locks(&mut self) -> ::protobuf::RepeatedField<ListResponse_Lock> {
         ::std::mem::replace(&mut self.locks, ::protobuf::RepeatedField::new())
     }
+
+    // string next_page_token = 2;
+
+
+    pub fn get_next_page_token(&self) -> &str {
+        &self.next_page_token
+    }
+    pub fn clear_next_page_token(&mut self) {
+        ::protobuf::Clear::clear(&mut self.next_page_token);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_next_page_token(&mut self, v: ::protobuf::Chars) {
+        self.next_page_token = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_next_page_token(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.next_page_token
+    }
+
+    // Take field
+    pub fn take_next_page_token(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.next_page_token, ::protobuf::Chars::new())
+    }
+
+    // uint64 revision = 3;
+
+
+    pub fn get_revision(&self) -> u64 {
+        self.revision
+    }
+    pub fn clear_revision(&mut self) {
+        self.revision = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_revision(&mut self, v: u64) {
+        self.revision = v;
+    }
 }
 
 impl ::protobuf::Message for ListResponse {
@@ -1251,6 +4645,16 @@ impl ::protobuf::Message for ListResponse {
                 1 => {
                     ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.locks)?;
                 },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.next_page_token)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.revision = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1267,6 +4671,12 @@ impl ::protobuf::Message for ListResponse {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        if !self.next_page_token.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.next_page_token);
+        }
+        if self.revision != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.revision, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1278,6 +4688,12 @@ impl ::protobuf::Message for ListResponse {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         };
+        if !self.next_page_token.is_empty() {
+            os.write_string(2, &self.next_page_token)?;
+        }
+        if self.revision != 0 {
+            os.write_uint64(3, self.revision)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1321,6 +4737,16 @@ impl ::protobuf::Message for ListResponse {
                 |m: &ListResponse| { &m.locks },
                 |m: &mut ListResponse| { &mut m.locks },
             ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "next_page_token",
+                |m: &ListResponse| { &m.next_page_token },
+                |m: &mut ListResponse| { &mut m.next_page_token },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "revision",
+                |m: &ListResponse| { &m.revision },
+                |m: &mut ListResponse| { &mut m.revision },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<ListResponse>(
                 "ListResponse",
                 fields,
@@ -1338,6 +4764,8 @@ impl ::protobuf::Message for ListResponse {
 impl ::protobuf::Clear for ListResponse {
     fn clear(&mut self) {
         self.locks.clear();
+        ::protobuf::Clear::clear(&mut self.next_page_token);
+        self.revision = 0;
         self.unknown_fields.clear();
     }
 }
@@ -1359,6 +4787,18 @@ pub struct ListResponse_Lock {
     // message fields
     pub name: ::protobuf::Chars,
     pub state: bool,
+    pub holder: ::protobuf::Chars,
+    pub expires_at: u64,
+    pub token: u64,
+    pub mode: Mode,
+    pub holders: ::std::vec::Vec<::protobuf::Chars>,
+    pub permits: u32,
+    pub used: u32,
+    pub waiters: ::std::vec::Vec<::protobuf::Chars>,
+    pub reentrant: bool,
+    pub holds: u32,
+    pub metadata: ::protobuf::SingularPtrField<Metadata>,
+    pub subtree: bool,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -1415,10 +4855,244 @@ impl ListResponse_Lock {
     pub fn set_state(&mut self, v: bool) {
         self.state = v;
     }
+
+    // string holder = 3;
+
+
+    pub fn get_holder(&self) -> &str {
+        &self.holder
+    }
+    pub fn clear_holder(&mut self) {
+        ::protobuf::Clear::clear(&mut self.holder);
+    }
+
+    // Param is passed by value, moved
+    pub fn set_holder(&mut self, v: ::protobuf::Chars) {
+        self.holder = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_holder(&mut self) -> &mut ::protobuf::Chars {
+        &mut self.holder
+    }
+
+    // Take field
+    pub fn take_holder(&mut self) -> ::protobuf::Chars {
+        ::std::mem::replace(&mut self.holder, ::protobuf::Chars::new())
+    }
+
+    // uint64 expires_at = 4;
+
+
+    pub fn get_expires_at(&self) -> u64 {
+        self.expires_at
+    }
+    pub fn clear_expires_at(&mut self) {
+        self.expires_at = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expires_at(&mut self, v: u64) {
+        self.expires_at = v;
+    }
+
+    // uint64 token = 5;
+
+
+    pub fn get_token(&self) -> u64 {
+        self.token
+    }
+    pub fn clear_token(&mut self) {
+        self.token = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_token(&mut self, v: u64) {
+        self.token = v;
+    }
+
+    // .api.grpc.Mode mode = 6;
+
+
+    pub fn get_mode(&self) -> Mode {
+        self.mode
+    }
+    pub fn clear_mode(&mut self) {
+        self.mode = Mode::Exclusive;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_mode(&mut self, v: Mode) {
+        self.mode = v;
+    }
+
+    // repeated string holders = 7;
+
+
+    pub fn get_holders(&self) -> &[::protobuf::Chars] {
+        &self.holders
+    }
+    pub fn clear_holders(&mut self) {
+        self.holders.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_holders(&mut self, v: ::std::vec::Vec<::protobuf::Chars>) {
+        self.holders = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_holders(&mut self) -> &mut ::std::vec::Vec<::protobuf::Chars> {
+        &mut self.holders
+    }
+
+    // Take field
+    pub fn take_holders(&mut self) -> ::std::vec::Vec<::protobuf::Chars> {
+        ::std::mem::replace(&mut self.holders, ::std::vec::Vec::new())
+    }
+
+    // uint32 permits = 8;
+
+
+    pub fn get_permits(&self) -> u32 {
+        self.permits
+    }
+    pub fn clear_permits(&mut self) {
+        self.permits = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_permits(&mut self, v: u32) {
+        self.permits = v;
+    }
+
+    // uint32 used = 9;
+
+
+    pub fn get_used(&self) -> u32 {
+        self.used
+    }
+    pub fn clear_used(&mut self) {
+        self.used = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_used(&mut self, v: u32) {
+        self.used = v;
+    }
+
+    // repeated string waiters = 10;
+
+
+    pub fn get_waiters(&self) -> &[::protobuf::Chars] {
+        &self.waiters
+    }
+    pub fn clear_waiters(&mut self) {
+        self.waiters.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_waiters(&mut self, v: ::std::vec::Vec<::protobuf::Chars>) {
+        self.waiters = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_waiters(&mut self) -> &mut ::std::vec::Vec<::protobuf::Chars> {
+        &mut self.waiters
+    }
+
+    // Take field
+    pub fn take_waiters(&mut self) -> ::std::vec::Vec<::protobuf::Chars> {
+        ::std::mem::replace(&mut self.waiters, ::std::vec::Vec::new())
+    }
+
+    // bool reentrant = 11;
+
+
+    pub fn get_reentrant(&self) -> bool {
+        self.reentrant
+    }
+    pub fn clear_reentrant(&mut self) {
+        self.reentrant = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_reentrant(&mut self, v: bool) {
+        self.reentrant = v;
+    }
+
+    // uint32 holds = 12;
+
+
+    pub fn get_holds(&self) -> u32 {
+        self.holds
+    }
+    pub fn clear_holds(&mut self) {
+        self.holds = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_holds(&mut self, v: u32) {
+        self.holds = v;
+    }
+
+    // .api.grpc.Metadata metadata = 13;
+
+
+    pub fn get_metadata(&self) -> &Metadata {
+        self.metadata.as_ref().unwrap_or_else(|| <Metadata as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_metadata(&mut self) {
+        self.metadata.clear();
+    }
+
+    pub fn has_metadata(&self) -> bool {
+        self.metadata.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_metadata(&mut self, v: Metadata) {
+        self.metadata = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_metadata(&mut self) -> &mut Metadata {
+        if self.metadata.is_none() {
+            self.metadata.set_default();
+        }
+        self.metadata.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_metadata(&mut self) -> Metadata {
+        self.metadata.take().unwrap_or_else(|| Metadata::new())
+    }
+
+    // bool subtree = 14;
+
+
+    pub fn get_subtree(&self) -> bool {
+        self.subtree
+    }
+    pub fn clear_subtree(&mut self) {
+        self.subtree = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_subtree(&mut self, v: bool) {
+        self.subtree = v;
+    }
 }
 
 impl ::protobuf::Message for ListResponse_Lock {
     fn is_initialized(&self) -> bool {
+        for v in &self.metadata {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -1436,6 +5110,70 @@ impl ::protobuf::Message for ListResponse_Lock {
                     let tmp = is.read_bool()?;
                     self.state = tmp;
                 },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_string_into(wire_type, is, &mut self.holder)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.expires_at = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.token = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.mode, 6, &mut self.unknown_fields)?
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_carllerche_string_into(wire_type, is, &mut self.holders)?;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.permits = tmp;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.used = tmp;
+                },
+                10 => {
+                    ::protobuf::rt::read_repeated_carllerche_string_into(wire_type, is, &mut self.waiters)?;
+                },
+                11 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.reentrant = tmp;
+                },
+                12 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.holds = tmp;
+                },
+                13 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.metadata)?;
+                },
+                14 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.subtree = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1454,6 +5192,43 @@ impl ::protobuf::Message for ListResponse_Lock {
         if self.state != false {
             my_size += 2;
         }
+        if !self.holder.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.holder);
+        }
+        if self.expires_at != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.expires_at, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.token != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.token, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.mode != Mode::Exclusive {
+            my_size += ::protobuf::rt::enum_size(6, self.mode);
+        }
+        for value in &self.holders {
+            my_size += ::protobuf::rt::string_size(7, &value);
+        };
+        if self.permits != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.permits, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.used != 0 {
+            my_size += ::protobuf::rt::value_size(9, self.used, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.waiters {
+            my_size += ::protobuf::rt::string_size(10, &value);
+        };
+        if self.reentrant != false {
+            my_size += 2;
+        }
+        if self.holds != 0 {
+            my_size += ::protobuf::rt::value_size(12, self.holds, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.metadata.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.subtree != false {
+            my_size += 2;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1466,6 +5241,44 @@ impl ::protobuf::Message for ListResponse_Lock {
         if self.state != false {
             os.write_bool(2, self.state)?;
         }
+        if !self.holder.is_empty() {
+            os.write_string(3, &self.holder)?;
+        }
+        if self.expires_at != 0 {
+            os.write_uint64(4, self.expires_at)?;
+        }
+        if self.token != 0 {
+            os.write_uint64(5, self.token)?;
+        }
+        if self.mode != Mode::Exclusive {
+            os.write_enum(6, ::protobuf::ProtobufEnum::value(&self.mode))?;
+        }
+        for v in &self.holders {
+            os.write_string(7, &v)?;
+        };
+        if self.permits != 0 {
+            os.write_uint32(8, self.permits)?;
+        }
+        if self.used != 0 {
+            os.write_uint32(9, self.used)?;
+        }
+        for v in &self.waiters {
+            os.write_string(10, &v)?;
+        };
+        if self.reentrant != false {
+            os.write_bool(11, self.reentrant)?;
+        }
+        if self.holds != 0 {
+            os.write_uint32(12, self.holds)?;
+        }
+        if let Some(ref v) = self.metadata.as_ref() {
+            os.write_tag(13, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if self.subtree != false {
+            os.write_bool(14, self.subtree)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1514,6 +5327,66 @@ impl ::protobuf::Message for ListResponse_Lock {
                 |m: &ListResponse_Lock| { &m.state },
                 |m: &mut ListResponse_Lock| { &mut m.state },
             ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "holder",
+                |m: &ListResponse_Lock| { &m.holder },
+                |m: &mut ListResponse_Lock| { &mut m.holder },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "expires_at",
+                |m: &ListResponse_Lock| { &m.expires_at },
+                |m: &mut ListResponse_Lock| { &mut m.expires_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "token",
+                |m: &ListResponse_Lock| { &m.token },
+                |m: &mut ListResponse_Lock| { &mut m.token },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Mode>>(
+                "mode",
+                |m: &ListResponse_Lock| { &m.mode },
+                |m: &mut ListResponse_Lock| { &mut m.mode },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "holders",
+                |m: &ListResponse_Lock| { &m.holders },
+                |m: &mut ListResponse_Lock| { &mut m.holders },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "permits",
+                |m: &ListResponse_Lock| { &m.permits },
+                |m: &mut ListResponse_Lock| { &mut m.permits },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "used",
+                |m: &ListResponse_Lock| { &m.used },
+                |m: &mut ListResponse_Lock| { &mut m.used },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheChars>(
+                "waiters",
+                |m: &ListResponse_Lock| { &m.waiters },
+                |m: &mut ListResponse_Lock| { &mut m.waiters },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "reentrant",
+                |m: &ListResponse_Lock| { &m.reentrant },
+                |m: &mut ListResponse_Lock| { &mut m.reentrant },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "holds",
+                |m: &ListResponse_Lock| { &m.holds },
+                |m: &mut ListResponse_Lock| { &mut m.holds },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Metadata>>(
+                "metadata",
+                |m: &ListResponse_Lock| { &m.metadata },
+                |m: &mut ListResponse_Lock| { &mut m.metadata },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "subtree",
+                |m: &ListResponse_Lock| { &m.subtree },
+                |m: &mut ListResponse_Lock| { &mut m.subtree },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<ListResponse_Lock>(
                 "ListResponse.Lock",
                 fields,
@@ -1532,6 +5405,18 @@ impl ::protobuf::Clear for ListResponse_Lock {
     fn clear(&mut self) {
         ::protobuf::Clear::clear(&mut self.name);
         self.state = false;
+        ::protobuf::Clear::clear(&mut self.holder);
+        self.expires_at = 0;
+        self.token = 0;
+        self.mode = Mode::Exclusive;
+        self.holders.clear();
+        self.permits = 0;
+        self.used = 0;
+        self.waiters.clear();
+        self.reentrant = false;
+        self.holds = 0;
+        self.metadata.clear();
+        self.subtree = false;
         self.unknown_fields.clear();
     }
 }
@@ -1548,31 +5433,157 @@ impl ::protobuf::reflect::ProtobufValue for ListResponse_Lock {
     }
 }
 
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Mode {
+    Exclusive = 0,
+    Shared = 1,
+}
+
+impl ::protobuf::ProtobufEnum for Mode {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Mode> {
+        match value {
+            0 => ::std::option::Option::Some(Mode::Exclusive),
+            1 => ::std::option::Option::Some(Mode::Shared),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [Mode] = &[
+            Mode::Exclusive,
+            Mode::Shared,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<Mode>("Mode", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for Mode {
+}
+
+impl ::std::default::Default for Mode {
+    fn default() -> Self {
+        Mode::Exclusive
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Mode {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\tapi.proto\x12\x08api.grpc\"\x07\n\x05Empty\"!\n\x0bLockRequest\x12\
-    \x12\n\x04name\x18\x01\x20\x01(\tR\x04name\"F\n\x0cLockResponse\x12\x16\
-    \n\x05error\x18\x01\x20\x01(\tH\0R\x05error\x12\x16\n\x05state\x18\x02\
-    \x20\x01(\x08H\0R\x05stateB\x06\n\x04body\"\x91\x01\n\tLockEvent\x12\x12\
-    \n\x04name\x18\x01\x20\x01(\tR\x04name\x122\n\x06status\x18\x02\x20\x01(\
-    \x0e2\x1a.api.grpc.LockEvent.StatusR\x06status\"<\n\x06Status\x12\x0b\n\
-    \x07Created\x10\0\x12\x0b\n\x07Removed\x10\x01\x12\x0c\n\x08Unlocked\x10\
-    \x02\x12\n\n\x06Locked\x10\x03\"\xad\x01\n\rPeersResponse\x122\n\x05peer\
-    s\x18\x01\x20\x03(\x0b2\x1c.api.grpc.PeersResponse.PeerR\x05peers\x1ah\n\
-    \x04Peer\x12\x16\n\x06status\x18\x01\x20\x01(\rR\x06status\x12'\n\x0fclu\
-    ster_address\x18\x02\x20\x01(\tR\x0eclusterAddress\x12\x1f\n\x0bapi_addr\
-    ess\x18\x03\x20\x01(\tR\napiAddress\"s\n\x0cListResponse\x121\n\x05locks\
-    \x18\x01\x20\x03(\x0b2\x1b.api.grpc.ListResponse.LockR\x05locks\x1a0\n\
-    \x04Lock\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04name\x12\x14\n\x05stat\
-    e\x18\x02\x20\x01(\x08R\x05state2\xb8\x03\n\x07Locking\x127\n\x06Create\
-    \x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.LockResponse\x127\n\x06Re\
-    move\x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.LockResponse\x12/\n\
-    \x04List\x12\x0f.api.grpc.Empty\x1a\x16.api.grpc.ListResponse\x125\n\x04\
-    Lock\x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.LockResponse\x127\n\
-    \x06Unlock\x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.LockResponse\
-    \x126\n\x05State\x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.LockRespo\
-    nse\x12/\n\x05Watch\x12\x0f.api.grpc.Empty\x1a\x13.api.grpc.LockEvent0\
+    \n\tapi.proto\x12\x08api.grpc\"\x07\n\x05Empty\"\xb9\x01\n\x08Metadata\
+    \x126\n\x06labels\x18\x01\x20\x03(\x0b2\x1e.api.grpc.Metadata.LabelsEntr\
+    yR\x06labels\x12\x20\n\x0bdescription\x18\x02\x20\x01(\tR\x0bdescription\
+    \x12\x18\n\x07payload\x18\x03\x20\x01(\x0cR\x07payload\x1a9\n\x0bLabelsE\
+    ntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\
+    \x02\x20\x01(\tR\x05value:\x028\x01\"\xa1\x02\n\x0bLockRequest\x12\x12\n\
+    \x04name\x18\x01\x20\x01(\tR\x04name\x12\x16\n\x06holder\x18\x02\x20\x01\
+    (\tR\x06holder\x12\x14\n\x05force\x18\x03\x20\x01(\x08R\x05force\x12\x10\
+    \n\x03ttl\x18\x04\x20\x01(\x04R\x03ttl\x12\x18\n\x07timeout\x18\x05\x20\
+    \x01(\x04R\x07timeout\x12\"\n\x04mode\x18\x06\x20\x01(\x0e2\x0e.api.grpc\
+    .ModeR\x04mode\x12\x18\n\x07permits\x18\x07\x20\x01(\rR\x07permits\x12\
+    \x1c\n\treentrant\x18\x08\x20\x01(\x08R\treentrant\x12.\n\x08metadata\
+    \x18\t\x20\x01(\x0b2\x12.api.grpc.MetadataR\x08metadata\x12\x18\n\x07sub\
+    tree\x18\n\x20\x01(\x08R\x07subtree\"\xcc\x01\n\x0bListRequest\x12\x16\n\
+    \x06prefix\x18\x01\x20\x01(\tR\x06prefix\x127\n\x05state\x18\x02\x20\x01\
+    (\x0e2!.api.grpc.ListRequest.StateFilterR\x05state\x12\x1b\n\tpage_size\
+    \x18\x03\x20\x01(\rR\x08pageSize\x12\x1d\n\npage_token\x18\x04\x20\x01(\
+    \tR\tpageToken\"0\n\x0bStateFilter\x12\x07\n\x03Any\x10\0\x12\n\n\x06Loc\
+    ked\x10\x01\x12\x0c\n\x08Unlocked\x10\x02\"k\n\x0fLockManyRequest\x12\
+    \x14\n\x05names\x18\x01\x20\x03(\tR\x05names\x12\x16\n\x06holder\x18\x02\
+    \x20\x01(\tR\x06holder\x12\x10\n\x03ttl\x18\x03\x20\x01(\x04R\x03ttl\x12\
+    \x18\n\x07timeout\x18\x04\x20\x01(\x04R\x07timeout\"*\n\x10LockManyRespo\
+    nse\x12\x16\n\x06tokens\x18\x01\x20\x03(\x04R\x06tokens\"o\n\x0fElection\
+    Request\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04name\x12\x1c\n\tcandida\
+    te\x18\x02\x20\x01(\tR\tcandidate\x12\x10\n\x03ttl\x18\x03\x20\x01(\x04R\
+    \x03ttl\x12\x18\n\x07timeout\x18\x04\x20\x01(\x04R\x07timeout\"P\n\x0eLe\
+    aderResponse\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04name\x12\x16\n\x06\
+    leader\x18\x02\x20\x01(\tR\x06leader\x12\x12\n\x04term\x18\x03\x20\x01(\
+    \x04R\x04term\"z\n\x0eBarrierRequest\x12\x12\n\x04name\x18\x01\x20\x01(\
+    \tR\x04name\x12\x20\n\x0bparticipant\x18\x02\x20\x01(\tR\x0bparticipant\
+    \x12\x18\n\x07parties\x18\x03\x20\x01(\rR\x07parties\x12\x18\n\x07timeou\
+    t\x18\x04\x20\x01(\x04R\x07timeout\"y\n\x0fBarrierResponse\x12\x12\n\x04\
+    name\x18\x01\x20\x01(\tR\x04name\x12\x18\n\x07parties\x18\x02\x20\x01(\r\
+    R\x07parties\x12\x18\n\x07arrived\x18\x03\x20\x01(\rR\x07arrived\x12\x1e\
+    \n\ngeneration\x18\x04\x20\x01(\x04R\ngeneration\"R\n\x0cLatchRequest\
+    \x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04name\x12\x14\n\x05count\x18\
+    \x02\x20\x01(\rR\x05count\x12\x18\n\x07timeout\x18\x03\x20\x01(\x04R\x07\
+    timeout\"9\n\rLatchResponse\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04nam\
+    e\x12\x14\n\x05count\x18\x02\x20\x01(\rR\x05count\"\xaa\x01\n\x0cLockRes\
+    ponse\x12\x16\n\x05error\x18\x01\x20\x01(\tH\0R\x05error\x12\x16\n\x05st\
+    ate\x18\x02\x20\x01(\x08H\0R\x05state\x12\x16\n\x05token\x18\x03\x20\x01\
+    (\x04H\0R\x05token\x12\x1a\n\x08position\x18\x04\x20\x01(\rR\x08position\
+    \x12.\n\x08metadata\x18\x05\x20\x01(\x0b2\x12.api.grpc.MetadataR\x08meta\
+    dataB\x06\n\x04body\"\xde\x02\n\tLockEvent\x12\x12\n\x04name\x18\x01\x20\
+    \x01(\tR\x04name\x122\n\x06status\x18\x02\x20\x01(\x0e2\x1a.api.grpc.Loc\
+    kEvent.StatusR\x06status\x12\x16\n\x06holder\x18\x03\x20\x01(\tR\x06hold\
+    er\x12\x20\n\x0bdescription\x18\x04\x20\x01(\tR\x0bdescription\x12\x1a\n\
+    \x08revision\x18\x05\x20\x01(\x04R\x08revision\"\xb2\x01\n\x06Status\x12\
+    \x0b\n\x07Created\x10\0\x12\x0b\n\x07Removed\x10\x01\x12\x0c\n\x08Unlock\
+    ed\x10\x02\x12\n\n\x06Locked\x10\x03\x12\x0b\n\x07Expired\x10\x04\x12\n\
+    \n\x06Queued\x10\x05\x12\x0c\n\x08Dequeued\x10\x06\x12\x0c\n\x08Deadlock\
+    \x10\x07\x12\x0b\n\x07Entered\x10\x08\x12\x0b\n\x07Tripped\x10\t\x12\x0f\
+    \n\x0bCountedDown\x10\n\x12\n\n\x06Opened\x10\x0b\x12\x08\n\x04Lost\x10\
+    \x0c\"\x99\x01\n\x0cWatchRequest\x12\x12\n\x04name\x18\x01\x20\x01(\tR\
+    \x04name\x12\x16\n\x06prefix\x18\x02\x20\x01(\tR\x06prefix\x126\n\x08sta\
+    tuses\x18\x03\x20\x03(\x0e2\x1a.api.grpc.LockEvent.StatusR\x08statuses\
+    \x12%\n\x0estart_revision\x18\x04\x20\x01(\x04R\rstartRevision\"\xad\x01\
+    \n\rPeersResponse\x122\n\x05peers\x18\x01\x20\x03(\x0b2\x1c.api.grpc.Pee\
+    rsResponse.PeerR\x05peers\x1ah\n\x04Peer\x12\x16\n\x06status\x18\x01\x20\
+    \x01(\rR\x06status\x12'\n\x0fcluster_address\x18\x02\x20\x01(\tR\x0eclus\
+    terAddress\x12\x1f\n\x0bapi_address\x18\x03\x20\x01(\tR\napiAddress\"\
+    \x89\x04\n\x0cListResponse\x121\n\x05locks\x18\x01\x20\x03(\x0b2\x1b.api\
+    .grpc.ListResponse.LockR\x05locks\x12&\n\x0fnext_page_token\x18\x02\x20\
+    \x01(\tR\rnextPageToken\x12\x1a\n\x08revision\x18\x03\x20\x01(\x04R\x08r\
+    evision\x1a\x81\x03\n\x04Lock\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04n\
+    ame\x12\x14\n\x05state\x18\x02\x20\x01(\x08R\x05state\x12\x16\n\x06holde\
+    r\x18\x03\x20\x01(\tR\x06holder\x12\x1d\n\nexpires_at\x18\x04\x20\x01(\
+    \x04R\texpiresAt\x12\x14\n\x05token\x18\x05\x20\x01(\x04R\x05token\x12\"\
+    \n\x04mode\x18\x06\x20\x01(\x0e2\x0e.api.grpc.ModeR\x04mode\x12\x18\n\
+    \x07holders\x18\x07\x20\x03(\tR\x07holders\x12\x18\n\x07permits\x18\x08\
+    \x20\x01(\rR\x07permits\x12\x12\n\x04used\x18\t\x20\x01(\rR\x04used\x12\
+    \x18\n\x07waiters\x18\n\x20\x03(\tR\x07waiters\x12\x1c\n\treentrant\x18\
+    \x0b\x20\x01(\x08R\treentrant\x12\x14\n\x05holds\x18\x0c\x20\x01(\rR\x05\
+    holds\x12.\n\x08metadata\x18\r\x20\x01(\x0b2\x12.api.grpc.MetadataR\x08m\
+    etadata\x12\x18\n\x07subtree\x18\x0e\x20\x01(\x08R\x07subtree*!\n\x04Mod\
+    e\x12\r\n\tExclusive\x10\0\x12\n\n\x06Shared\x10\x012\x81\n\n\x07Locking\
+    \x127\n\x06Create\x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.LockResp\
+    onse\x127\n\x06Remove\x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.Lock\
+    Response\x125\n\x04List\x12\x15.api.grpc.ListRequest\x1a\x16.api.grpc.Li\
+    stResponse\x125\n\x04Lock\x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.\
+    LockResponse\x128\n\x07Acquire\x12\x15.api.grpc.LockRequest\x1a\x16.api.\
+    grpc.LockResponse\x12D\n\x0bAcquireMany\x12\x19.api.grpc.LockManyRequest\
+    \x1a\x1a.api.grpc.LockManyResponse\x127\n\x06Unlock\x12\x15.api.grpc.Loc\
+    kRequest\x1a\x16.api.grpc.LockResponse\x12:\n\tKeepAlive\x12\x15.api.grp\
+    c.LockRequest\x1a\x16.api.grpc.LockResponse\x12B\n\x0fKeepAliveStream\
+    \x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.LockResponse(\x01\x126\n\
+    \x05State\x12\x15.api.grpc.LockRequest\x1a\x16.api.grpc.LockResponse\x12\
+    6\n\x05Watch\x12\x16.api.grpc.WatchRequest\x1a\x13.api.grpc.LockEvent0\
     \x01\x121\n\x05Peers\x12\x0f.api.grpc.Empty\x1a\x17.api.grpc.PeersRespon\
-    seb\x06proto3\
+    se\x12?\n\x08Campaign\x12\x19.api.grpc.ElectionRequest\x1a\x18.api.grpc.\
+    LeaderResponse\x12=\n\x06Resign\x12\x19.api.grpc.ElectionRequest\x1a\x18\
+    .api.grpc.LeaderResponse\x12=\n\x06Leader\x12\x19.api.grpc.ElectionReque\
+    st\x1a\x18.api.grpc.LeaderResponse\x12@\n\x07Observe\x12\x19.api.grpc.El\
+    ectionRequest\x1a\x18.api.grpc.LeaderResponse0\x01\x12D\n\rCreateBarrier\
+    \x12\x18.api.grpc.BarrierRequest\x1a\x19.api.grpc.BarrierResponse\x12<\n\
+    \x05Enter\x12\x18.api.grpc.BarrierRequest\x1a\x19.api.grpc.BarrierRespon\
+    se\x12>\n\x0bCreateLatch\x12\x16.api.grpc.LatchRequest\x1a\x17.api.grpc.\
+    LatchResponse\x12<\n\tCountDown\x12\x16.api.grpc.LatchRequest\x1a\x17.ap\
+    i.grpc.LatchResponse\x127\n\x04Wait\x12\x16.api.grpc.LatchRequest\x1a\
+    \x17.api.grpc.LatchResponseb\x06proto3\
 ";
 
 static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
@@ -1,5 +1,5 @@
 pub mod api;
 pub use api::{
-    Empty, ListResponse, ListResponse_Lock, LockEvent, LockRequest, LockResponse, PeersResponse,
-    PeersResponse_Peer,
+    Empty, ListRequest, ListResponse, ListResponse_Lock, LockEvent, LockRequest, LockResponse, PeersResponse,
+    Mode, PeersResponse_Peer,
 };